@@ -1,8 +1,25 @@
-trait AMinMax {
-    fn amin(&mut self, x: Self);
-    fn amax(&mut self, x: Self);
-}
-impl<T: PartialOrd> AMinMax for T {
-    fn amin(&mut self, x: Self) { if *self > x { *self = x; } }
-    fn amax(&mut self, x: Self) { if *self < x { *self = x; } }
+pub mod amin_max {
+    pub trait AMinMax {
+	fn amin(&mut self, x: Self);
+	fn amax(&mut self, x: Self);
+    }
+    impl<T: PartialOrd> AMinMax for T {
+	fn amin(&mut self, x: Self) { if *self > x { *self = x; } }
+	fn amax(&mut self, x: Self) { if *self < x { *self = x; } }
+    }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+
+	#[test]
+	fn amin_amax() {
+	    let (mut lo, mut hi) = (5, 5);
+	    for x in [3, 8, 4] {
+		lo.amin(x);
+		hi.amax(x);
+	    }
+	    assert_eq!((lo, hi), (3, 8));
+	}
+    }
 }
@@ -1,4 +1,4 @@
-mod bipartite_matching {
+pub mod bipartite_matching {
     // Hopcroft-Karp algorithm;
     // from_left(x) = y <=> from_right(y) = x
     // O(E sqrt(V));
@@ -110,4 +110,46 @@ mod bipartite_matching {
 	    self.count
 	}
     }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+
+	fn brute(adj: &Vec<Vec<bool>>, i: usize, used: &mut Vec<bool>) -> usize {
+	    if i == adj.len() { return 0; }
+	    let mut best = brute(adj, i + 1, used);
+	    for j in 0..used.len() {
+		if adj[i][j] && !used[j] {
+		    used[j] = true;
+		    best = best.max(1 + brute(adj, i + 1, used));
+		    used[j] = false;
+		}
+	    }
+	    best
+	}
+
+	#[test]
+	fn all_graphs_3x3() {
+	    for mask in 0..1 << 9 {
+		let adj: Vec<Vec<bool>> = (0..3).map(|i| (0..3).map(|j| mask >> (i * 3 + j) & 1 == 1).collect()).collect();
+		let mut bm = BipartiteMatching::new(3, 3);
+		for i in 0..3 {
+		    for j in 0..3 {
+			if adj[i][j] { bm.add_edge(i, j); }
+		    }
+		}
+		let m = bm.solve();
+		assert_eq!(m.count(), brute(&adj, 0, &mut vec![false; 3]));
+		let mut count = 0;
+		for i in 0..3 {
+		    if let Some(j) = m.from_left(i) {
+			assert!(adj[i][j]);
+			assert_eq!(m.from_right(j), Some(i));
+			count += 1;
+		    }
+		}
+		assert_eq!(count, m.count());
+	    }
+	}
+    }
 }
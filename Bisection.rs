@@ -1,35 +1,50 @@
-trait Bisection<T> {
-    fn lower_bound(&self, x: T) -> usize;
-    fn upper_bound(&self, x: T) -> usize;
-}
+pub mod bisection {
+    pub trait Bisection<T> {
+        fn lower_bound(&self, x: T) -> usize;
+        fn upper_bound(&self, x: T) -> usize;
+    }
 
-impl<T: std::cmp::PartialOrd> Bisection<T> for Vec<T> {
-    fn lower_bound(&self, x: T) -> usize {
-        let mut left = 0;
-        let mut right = self.len();
-        while left != right {
-            let middle = (left + right) / 2;
-            if self[middle] < x {
-                left = middle + 1;
-            } else {
-                right = middle;
+    impl<T: std::cmp::PartialOrd> Bisection<T> for Vec<T> {
+        fn lower_bound(&self, x: T) -> usize {
+            let mut left = 0;
+            let mut right = self.len();
+            while left != right {
+                let middle = (left + right) / 2;
+                if self[middle] < x {
+                    left = middle + 1;
+                } else {
+                    right = middle;
+                }
             }
+            left
         }
-        left
-    }
 
-    fn upper_bound(&self, x: T) -> usize {
-        let mut left = 0;
-        let mut right = self.len();
-        while left != right {
-            let middle = (left + right) / 2;
-            if x < self[middle] {
-                right = middle;
-            } else {
-                left = middle + 1;
+        fn upper_bound(&self, x: T) -> usize {
+            let mut left = 0;
+            let mut right = self.len();
+            while left != right {
+                let middle = (left + right) / 2;
+                if x < self[middle] {
+                    right = middle;
+                } else {
+                    left = middle + 1;
+                }
             }
+            left
         }
-        left
     }
-}
 
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn bounds() {
+            let v = vec![1, 1, 2, 4, 4, 4, 7];
+            for x in 0..9 {
+                assert_eq!(v.lower_bound(x), v.iter().filter(|&&y| y < x).count());
+                assert_eq!(v.upper_bound(x), v.iter().filter(|&&y| y <= x).count());
+            }
+        }
+    }
+} // mod bisection;
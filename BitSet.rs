@@ -1,4 +1,4 @@
-mod bitset {
+pub mod bitset {
     #[derive(Clone, Debug)]
     pub struct BitSet {
 	b: Vec<u64>,
@@ -72,4 +72,34 @@ mod bitset {
 	    }
 	}
     }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+
+	#[test]
+	fn ops() {
+	    let n = 150;
+	    let mut a = BitSet::with_bits(n);
+	    let mut b = BitSet::with_bits(n);
+	    let mut va = vec![false; n];
+	    let mut vb = vec![false; n];
+	    for i in 0..n {
+		if i % 3 == 0 { a.set(i); va[i] = true; }
+		if i % 5 == 0 { b.set(i); vb[i] = true; }
+	    }
+	    a.reset(30);
+	    va[30] = false;
+	    let (or, and, xor) = (&a | &b, &a & &b, &a ^ &b);
+	    let mut c = a.clone();
+	    c ^= &b;
+	    for i in 0..n {
+		assert_eq!(a.test(i), va[i]);
+		assert_eq!(or.test(i), va[i] | vb[i]);
+		assert_eq!(and.test(i), va[i] & vb[i]);
+		assert_eq!(xor.test(i), va[i] ^ vb[i]);
+		assert_eq!(c.test(i), va[i] ^ vb[i]);
+	    }
+	}
+    }
 }
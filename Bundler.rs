@@ -0,0 +1,410 @@
+// Inlines the library modules used by a solution into one submission file.
+//   cargo run --bin bundler -- solution.rs [library_dir] > submit.rs
+// library_dir defaults to this crate's root.
+//
+// A library file provides the modules declared at its top level (`mod fenwick { .. }`)
+// and the macros it defines.  The solution pulls a module in by mentioning
+// `crate::fenwick` (also `use crate::{fenwick, scc}`) or by invoking one of its macros,
+// and each pulled file is scanned the same way, so dependencies come along transitively.
+// Demo `fn main`s and `#[cfg(test)]` items are removed from the library files.
+// Library files are written before the solution, dependencies first,
+// so macro_rules! are defined before they are used.
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+struct Source {
+    chars: Vec<char>,
+    // code[i]: chars[i] is neither in a comment nor in a string or char literal.
+    code: Vec<bool>,
+}
+
+impl Source {
+    fn new(text: &str) -> Self {
+	let chars: Vec<char> = text.chars().collect();
+	let n = chars.len();
+	let mut code = vec![true; n];
+	let at = |i: usize| if i < n { chars[i] } else { '\0' };
+	let mut i = 0;
+	while i < n {
+	    let start = i;
+	    if chars[i] == '/' && at(i + 1) == '/' {
+		while i < n && chars[i] != '\n' { i += 1; }
+	    } else if chars[i] == '/' && at(i + 1) == '*' {
+		let mut depth = 0;
+		while i < n {
+		    if chars[i] == '/' && at(i + 1) == '*' {
+			depth += 1;
+			i += 2;
+		    } else if chars[i] == '*' && at(i + 1) == '/' {
+			depth -= 1;
+			i += 2;
+			if depth == 0 { break; }
+		    } else {
+			i += 1;
+		    }
+		}
+	    } else if chars[i] == 'r' && (at(i + 1) == '"' || (at(i + 1) == '#' && matches!(at(i + 2), '"' | '#')))
+		&& (i == 0 || !is_ident(chars[i - 1]) || (chars[i - 1] == 'b' && (i == 1 || !is_ident(chars[i - 2])))) {
+		// raw string r#"..."#
+		i += 1;
+		let mut hashes = 0;
+		while at(i) == '#' { hashes += 1; i += 1; }
+		i += 1;
+		while i < n {
+		    if chars[i] == '"' && (1..=hashes).all(|k| at(i + k) == '#') {
+			i += 1 + hashes;
+			break;
+		    }
+		    i += 1;
+		}
+	    } else if chars[i] == '"' {
+		i += 1;
+		while i < n && chars[i] != '"' {
+		    if chars[i] == '\\' { i += 1; }
+		    i += 1;
+		}
+		i += 1;
+	    } else if chars[i] == '\'' && (at(i + 1) == '\\' || at(i + 2) == '\'') {
+		// char literal; otherwise it is a lifetime or a label
+		i += 1;
+		if chars[i] == '\\' { i += 1; }
+		i += 1;
+		while i < n && chars[i] != '\'' { i += 1; }
+		i += 1;
+	    } else {
+		i += 1;
+		continue;
+	    }
+	    i = i.min(n);
+	    code[start..i].iter_mut().for_each(|c| *c = false);
+	}
+	Self { chars, code }
+    }
+
+    fn is_code(&self, i: usize, c: char) -> bool {
+	i < self.chars.len() && self.code[i] && self.chars[i] == c
+    }
+
+    fn starts_with(&self, i: usize, word: &str) -> bool {
+	word.chars().enumerate().all(|(k, c)| self.is_code(i + k, c))
+    }
+
+    // `word` at i as a whole word.
+    fn word_at(&self, i: usize, word: &str) -> bool {
+	self.starts_with(i, word)
+	    && (i == 0 || !is_ident(self.chars[i - 1]))
+	    && !self.chars.get(i + word.chars().count()).is_some_and(|&c| is_ident(c))
+    }
+
+    fn skip_space(&self, mut i: usize) -> usize {
+	while i < self.chars.len() && (self.chars[i].is_whitespace() || !self.code[i]) { i += 1; }
+	i
+    }
+
+    fn ident(&self, i: usize) -> String {
+	self.chars[i..].iter().take_while(|&&c| is_ident(c)).collect()
+    }
+
+    // Brace depth before each char.
+    fn depth(&self) -> Vec<i32> {
+	let mut d = 0;
+	let mut ret = Vec::with_capacity(self.chars.len());
+	for i in 0..self.chars.len() {
+	    ret.push(d);
+	    if self.is_code(i, '{') { d += 1; }
+	    if self.is_code(i, '}') { d -= 1; }
+	}
+	ret
+    }
+
+    // End (exclusive) of the item starting at i: its first `;` at depth 0 or its matching `}`.
+    fn item_end(&self, mut i: usize) -> usize {
+	let mut d = 0;
+	while i < self.chars.len() {
+	    if self.is_code(i, '{') { d += 1; }
+	    if self.is_code(i, '}') {
+		d -= 1;
+		if d == 0 { return i + 1; }
+	    }
+	    if self.is_code(i, ';') && d == 0 { return i + 1; }
+	    i += 1;
+	}
+	i
+    }
+
+    // Items (with their attributes) that should not be bundled:
+    // `#[cfg(test)]` items anywhere and `fn main` at the top level.
+    fn removed_ranges(&self) -> Vec<(usize, usize)> {
+	let depth = self.depth();
+	let mut ret = Vec::new();
+	let mut i = 0;
+	while i < self.chars.len() {
+	    if self.starts_with(i, "#[cfg(test)]") {
+		let item = self.skip_attributes(i);
+		let end = self.item_end(item);
+		ret.push((i, end));
+		i = end;
+	    } else if depth[i] == 0 && self.word_at(i, "fn") && self.ident(self.skip_space(i + 2)) == "main" {
+		let end = self.item_end(i);
+		ret.push((self.attributes_start(i), end));
+		i = end;
+	    } else {
+		i += 1;
+	    }
+	}
+	ret
+    }
+
+    fn skip_attributes(&self, mut i: usize) -> usize {
+	i = self.skip_space(i);
+	while self.is_code(i, '#') {
+	    let mut d = 0;
+	    while i < self.chars.len() {
+		if self.is_code(i, '[') { d += 1; }
+		if self.is_code(i, ']') {
+		    d -= 1;
+		    if d == 0 { break; }
+		}
+		i += 1;
+	    }
+	    i = self.skip_space(i + 1);
+	}
+	i
+    }
+
+    // Start of the attribute lines directly above the item at i.
+    fn attributes_start(&self, i: usize) -> usize {
+	let mut start = i;
+	let text: String = self.chars[..i].iter().collect();
+	let mut lines: Vec<&str> = text.split('\n').collect();
+	lines.pop();
+	while let Some(line) = lines.pop() {
+	    let t = line.trim();
+	    if t.starts_with("#[") && t.ends_with(']') {
+		start -= line.chars().count() + 1;
+	    } else {
+		break;
+	    }
+	}
+	start
+    }
+
+    fn without(&self, ranges: &[(usize, usize)]) -> String {
+	let mut keep = vec![true; self.chars.len()];
+	for &(l, r) in ranges {
+	    // also drop the indentation and blank line before the item and the newline after it
+	    let mut l = l;
+	    while l > 0 && (self.chars[l - 1] == ' ' || self.chars[l - 1] == '\t') { l -= 1; }
+	    if l >= 2 && self.chars[l - 1] == '\n' && self.chars[l - 2] == '\n' { l -= 1; }
+	    let r = if self.chars.get(r) == Some(&'\n') { r + 1 } else { r };
+	    keep[l..r].iter_mut().for_each(|k| *k = false);
+	}
+	let text: String = self.chars.iter().zip(keep).filter(|&(_, k)| k).map(|(&c, _)| c).collect();
+	let mut ret = String::new();
+	let mut blank = 0;
+	for line in text.trim().lines() {
+	    blank = if line.trim().is_empty() { blank + 1 } else { 0 };
+	    if blank <= 1 {
+		ret.push_str(line.trim_end());
+		ret.push('\n');
+	    }
+	}
+	ret
+    }
+
+    // Names of the modules declared at the top level.
+    fn modules(&self) -> Vec<String> {
+	let depth = self.depth();
+	(0..self.chars.len())
+	    .filter(|&i| depth[i] == 0 && self.word_at(i, "mod"))
+	    .map(|i| self.ident(self.skip_space(i + 3)))
+	    .collect()
+    }
+
+    fn macros(&self) -> Vec<String> {
+	(0..self.chars.len())
+	    .filter(|&i| self.word_at(i, "macro_rules") && self.is_code(i + 11, '!'))
+	    .map(|i| self.ident(self.skip_space(i + 12)))
+	    .collect()
+    }
+
+    // Module names after `crate::`, including every name in `crate::{a, b::c}`.
+    fn crate_refs(&self) -> BTreeSet<String> {
+	let mut ret = BTreeSet::new();
+	for i in 0..self.chars.len() {
+	    if !self.starts_with(i, "crate::") || (i > 0 && is_ident(self.chars[i - 1])) { continue; }
+	    let j = self.skip_space(i + 7);
+	    if !self.is_code(j, '{') {
+		ret.insert(self.ident(j));
+		continue;
+	    }
+	    let mut d = 0;
+	    let mut k = j;
+	    while k < self.chars.len() {
+		if self.is_code(k, '{') || self.is_code(k, ',') {
+		    if self.is_code(k, '{') { d += 1; }
+		    if d == 1 { ret.insert(self.ident(self.skip_space(k + 1))); }
+		} else if self.is_code(k, '}') {
+		    d -= 1;
+		    if d == 0 { break; }
+		}
+		k += 1;
+	    }
+	}
+	ret.remove("");
+	ret
+    }
+
+    fn macro_calls(&self, names: &BTreeMap<String, usize>) -> BTreeSet<String> {
+	let mut ret = BTreeSet::new();
+	for i in 0..self.chars.len() {
+	    if self.code[i] && is_ident(self.chars[i]) && (i == 0 || !is_ident(self.chars[i - 1])) {
+		let name = self.ident(i);
+		if names.contains_key(&name) && self.is_code(i + name.chars().count(), '!') {
+		    ret.insert(name);
+		}
+	    }
+	}
+	ret
+    }
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+struct Library {
+    texts: Vec<String>,
+    deps: Vec<(BTreeSet<String>, BTreeSet<String>)>, // (crate::refs, macro calls)
+    module_file: BTreeMap<String, usize>,
+    macro_file: BTreeMap<String, usize>,
+}
+
+impl Library {
+    fn load(dir: &Path, exclude: &Path) -> Self {
+	let mut paths: Vec<_> = std::fs::read_dir(dir)
+	    .unwrap_or_else(|e| panic!("ERROR: cannot read {}: {}", dir.display(), e))
+	    .map(|e| e.unwrap().path())
+	    .filter(|p| p.extension().is_some_and(|e| e == "rs"))
+	    .filter(|p| p.canonicalize().ok() != exclude.canonicalize().ok())
+	    .collect();
+	paths.sort();
+	let mut lib = Library { texts: Vec::new(), deps: Vec::new(), module_file: BTreeMap::new(), macro_file: BTreeMap::new() };
+	let mut sources = Vec::new();
+	for path in &paths {
+	    let text = std::fs::read_to_string(path).unwrap();
+	    let src = Source::new(&text);
+	    let src = Source::new(&src.without(&src.removed_ranges()));
+	    let id = lib.texts.len();
+	    for name in src.modules() {
+		if let Some(&other) = lib.module_file.get(&name) {
+		    eprintln!("warning: mod {} is in both {} and {}", name, paths[other].display(), path.display());
+		} else {
+		    lib.module_file.insert(name, id);
+		}
+	    }
+	    for name in src.macros() {
+		lib.macro_file.entry(name).or_insert(id);
+	    }
+	    lib.texts.push(src.chars.iter().collect());
+	    sources.push(src);
+	}
+	for src in &sources {
+	    lib.deps.push((src.crate_refs(), src.macro_calls(&lib.macro_file)));
+	}
+	lib
+    }
+
+    fn files_of(&self, refs: &BTreeSet<String>, calls: &BTreeSet<String>) -> Vec<usize> {
+	let mut ret: Vec<usize> = refs.iter().filter_map(|m| self.module_file.get(m)).copied().collect();
+	ret.extend(calls.iter().filter_map(|m| self.macro_file.get(m)));
+	ret
+    }
+
+    // Post order, so every file comes after the files it depends on.
+    fn visit(&self, v: usize, skip: &BTreeSet<usize>, vis: &mut Vec<bool>, order: &mut Vec<usize>) {
+	if vis[v] || skip.contains(&v) { return; }
+	vis[v] = true;
+	let (refs, calls) = &self.deps[v];
+	for w in self.files_of(refs, calls) {
+	    self.visit(w, skip, vis, order);
+	}
+	order.push(v);
+    }
+}
+
+// The submission file for `solution`, with the library files it needs in front.
+fn bundle(solution: &str, lib: &Library) -> String {
+    let src = Source::new(solution);
+    let own: BTreeSet<String> = src.modules().into_iter().chain(src.macros()).collect();
+    let skip: BTreeSet<usize> = own.iter()
+	.filter_map(|name| lib.module_file.get(name).or_else(|| lib.macro_file.get(name)))
+	.copied()
+	.collect();
+    let mut vis = vec![false; lib.texts.len()];
+    let mut order = Vec::new();
+    for v in lib.files_of(&src.crate_refs(), &src.macro_calls(&lib.macro_file)) {
+	lib.visit(v, &skip, &mut vis, &mut order);
+    }
+    let mut ret = String::new();
+    for v in order {
+	ret.push_str(&lib.texts[v]);
+	ret.push('\n');
+    }
+    ret.push_str(solution);
+    ret
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 2 || args.len() > 3 {
+	eprintln!("usage: {} solution.rs [library_dir] > submit.rs", args[0]);
+	std::process::exit(2);
+    }
+    let solution_path = Path::new(&args[1]);
+    let dir = Path::new(args.get(2).map_or(option_env!("CARGO_MANIFEST_DIR").unwrap_or("."), |s| s.as_str()));
+    let solution = std::fs::read_to_string(solution_path)
+	.unwrap_or_else(|e| panic!("ERROR: cannot read {}: {}", solution_path.display(), e));
+    let lib = Library::load(dir, solution_path);
+    print!("{}", bundle(&solution, &lib));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_solution_compiles() {
+	// polynomial pulls in mod_int; the solution has its own `Mint` and `main`
+	let solution = r#"use crate::polynomial::Polynomial;
+type Mint = crate::mod_int::ModInt998244353;
+
+fn main() {
+    input!{ n: usize }
+    let p = Polynomial::from(vec![Mint::new(1), Mint::new(n as u64)]);
+    println!("{:?}", (&p * &p).get(1));
+}
+"#;
+	let dir = Path::new(option_env!("CARGO_MANIFEST_DIR").unwrap_or("."));
+	let lib = Library::load(dir, Path::new("solution.rs"));
+	let out = bundle(solution, &lib);
+	for m in ["mod polynomial", "mod mod_int", "macro_rules! input"] {
+	    assert!(out.contains(m), "ERROR: {} is not bundled", m);
+	}
+	assert!(!out.contains("mod stress"));
+	assert!(!out.contains("#[cfg(test)]"));
+
+	let tmp = std::env::temp_dir().join(format!("bundler_test_{}", std::process::id()));
+	std::fs::create_dir_all(&tmp).unwrap();
+	let file = tmp.join("submit.rs");
+	std::fs::write(&file, &out).unwrap();
+	let status = std::process::Command::new(std::env::var("RUSTC").unwrap_or("rustc".to_string()))
+	    .args(["--edition", "2021", "--cap-lints", "allow", "-o"])
+	    .arg(tmp.join("submit"))
+	    .arg(&file)
+	    .status()
+	    .unwrap();
+	std::fs::remove_dir_all(&tmp).unwrap();
+	assert!(status.success(), "ERROR: bundled solution does not compile");
+    }
+}
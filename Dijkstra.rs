@@ -1,44 +1,83 @@
-#[derive(Debug, Clone)]
-struct Dijkstra (Vec<Vec<(usize, i64)>>);
+pub mod dijkstra {
+    #[derive(Debug, Clone)]
+    pub struct Dijkstra(Vec<Vec<(usize, i64)>>);
 
-impl Dijkstra {
-    pub fn new(n: usize) -> Self {
-        Self(vec![Vec::new(); n])
-    }
+    impl Dijkstra {
+        pub fn new(n: usize) -> Self {
+            Self(vec![Vec::new(); n])
+        }
 
-    pub fn add_edge(&mut self, x: usize, y: usize, c: i64) {
-        self.0[x].push((y, c));
-    }
+        pub fn add_edge(&mut self, x: usize, y: usize, c: i64) {
+            self.0[x].push((y, c));
+        }
 
-    pub fn len(&self) -> usize {
-        self.0.len()
+        pub fn len(&self) -> usize {
+            self.0.len()
+        }
+
+        pub fn solve(&self, source: usize) -> Vec<i64> {
+            use std::cmp::Reverse;
+
+            let mut dist = vec![i64::MAX; self.len()];
+            dist[source] = 0;
+            let mut heap = std::collections::BinaryHeap::new();
+            heap.push(Reverse((0, source)));
+            while let Some(Reverse((d, v))) = heap.pop() {
+                if dist[v] == d {
+                    for &(to, c) in self.0[v].iter() {
+                        if dist[to] > d + c {
+                            dist[to] = d + c;
+                            heap.push(Reverse((dist[to], to)));
+                        }
+                    }
+                }
+            }
+            dist
+        }
     }
 
-    pub fn solve(&self, source: usize) -> Vec<i64> {
-        use std::cmp::Reverse;
-
-        let mut dist = vec![i64::MAX; self.len()];
-        dist[source] = 0;
-        let mut heap = std::collections::BinaryHeap::new();
-        heap.push(Reverse((0, source)));
-        while let Some(Reverse((d, v))) = heap.pop() {
-            if dist[v] == d {
-                for &(to, c) in self.0[v].iter() {
-                    if dist[to] > d + c {
-                        dist[to] = d + c;
-                        heap.push(Reverse((dist[to], to)));
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::stress::XorShift;
+
+        #[test]
+        fn path() {
+            let mut g = Dijkstra::new(6);
+            g.add_edge(0, 2, 10);
+            g.add_edge(2, 5, 20);
+            g.add_edge(5, 3, 12);
+            g.add_edge(0, 3, 50);
+            assert_eq!(g.solve(0), vec![0, i64::MAX, 10, 42, i64::MAX, 30]);
+        }
+
+        #[test]
+        fn against_floyd_warshall() {
+            let n = 7;
+            let mut rng = XorShift::new(1);
+            for _ in 0..50 {
+                let mut g = Dijkstra::new(n);
+                let mut d = vec![vec![i64::MAX; n]; n];
+                for i in 0..n { d[i][i] = 0; }
+                for _ in 0..15 {
+                    let x = rng.next_u64();
+                    let (a, b, c) = (x as usize % n, (x >> 8) as usize % n, (x >> 16) as i64 % 100);
+                    g.add_edge(a, b, c);
+                    d[a][b] = d[a][b].min(c);
+                }
+                for k in 0..n {
+                    for i in 0..n {
+                        for j in 0..n {
+                            if d[i][k] < i64::MAX && d[k][j] < i64::MAX {
+                                d[i][j] = d[i][j].min(d[i][k] + d[k][j]);
+                            }
+                        }
                     }
                 }
+                for s in 0..n {
+                    assert_eq!(g.solve(s), d[s]);
+                }
             }
         }
-        dist
     }
-}
-
-fn main() {
-    let mut g = Dijkstra::new(6);
-    g.add_edge(0, 2, 10);
-    g.add_edge(2, 5, 20);
-    g.add_edge(5, 3, 12);
-    println!("{:?}", g.solve(0));
-}
+} // mod dijkstra;
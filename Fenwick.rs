@@ -1,4 +1,4 @@
-mod fenwick {
+pub mod fenwick {
     use std::ops::{ Sub, AddAssign };
 
     pub struct Fenwick<T> { d: Vec<T> }
@@ -28,4 +28,28 @@ mod fenwick {
 	    self.prefix_sum(r) - self.prefix_sum(l)
 	}
     }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+	use crate::stress::XorShift;
+
+	#[test]
+	fn against_array() {
+	    let n = 20;
+	    let mut f = Fenwick::<i64>::new(n);
+	    let mut a = vec![0; n];
+	    let mut rng = XorShift::new(1);
+	    for _ in 0..300 {
+		let x = rng.next_u64();
+		let (i, v) = (x as usize % n, (x >> 8) as i64 % 100 - 50);
+		f.add(i, v);
+		a[i] += v;
+		let (l, r) = ((x >> 16) as usize % (n + 1), (x >> 24) as usize % (n + 1));
+		let (l, r) = (l.min(r), l.max(r));
+		assert_eq!(f.sum(l, r), a[l..r].iter().sum::<i64>());
+		assert_eq!(f.prefix_sum(r), a[..r].iter().sum::<i64>());
+	    }
+	}
+    }
 }
@@ -1,4 +1,4 @@
-mod grid {
+pub mod grid {
     use std::ops::{Index, IndexMut};
 
     #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -19,8 +19,15 @@ mod grid {
 	pub fn width(&self) -> usize {
 	    self.width
 	}
+	// Keeps g[i][j] for i < min(height, new_height), j < min(width, new_width).
 	pub fn resize(&mut self, new_height: usize, new_width: usize, value: T) where T: Clone {
-	    self.a.resize(new_height * new_width, value);
+	    let mut a = Vec::with_capacity(new_height * new_width);
+	    for i in 0..new_height {
+		for j in 0..new_width {
+		    a.push(if i < self.height && j < self.width { self.a[i * self.width + j].clone() } else { value.clone() });
+		}
+	    }
+	    *self = Self { height: new_height, width: new_width, a };
 	}
 	pub fn fill(&mut self, value: T) where T: Clone {
 	    self.a.fill(value);
@@ -41,4 +48,41 @@ mod grid {
 	    &mut self.a[i * self.width .. (i + 1) * self.width]
 	}
     }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+
+	#[test]
+	fn rows() {
+	    let mut g = Grid::new(2, 3, 0);
+	    g[1][2] = 5;
+	    g[0][0] = 1;
+	    assert_eq!((g.height(), g.width()), (2, 3));
+	    assert_eq!(&g[0], &[1, 0, 0]);
+	    assert_eq!(&g[1], &[0, 0, 5]);
+	    g.fill(7);
+	    assert_eq!(&g[1], &[7, 7, 7]);
+	}
+
+	#[test]
+	fn resize() {
+	    let mut g = Grid::new(2, 2, 1);
+	    g[1][0] = 2;
+	    g.resize(3, 3, 0);
+	    assert_eq!((g.height(), g.width()), (3, 3));
+	    assert_eq!(&g[0], &[1, 1, 0]);
+	    assert_eq!(&g[1], &[2, 1, 0]);
+	    assert_eq!(&g[2], &[0, 0, 0]);
+	    g.resize(1, 1, 0);
+	    assert_eq!(&g[0], &[1]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn out_of_bounds() {
+	    let g = Grid::new(2, 3, 0);
+	    let _ = &g[2];
+	}
+    }
 } // mod grid;
@@ -0,0 +1,217 @@
+#[macro_use]
+pub mod input {
+    use std::cell::RefCell;
+    use std::io::{BufRead, StdinLock};
+    use std::str::FromStr;
+
+    // Whitespace separated tokens of any FromStr type, see Tokenizer.
+    // The reader is pulled one line at a time only when the buffer runs out,
+    // so values may be laid out across lines in any way.
+    pub struct Scanner<R> {
+	reader: R,
+	buf: Vec<u8>,
+	pos: usize,
+	label: &'static str,
+    }
+
+    impl Scanner<StdinLock<'static>> {
+	pub fn stdin() -> Self {
+	    Self::new(std::io::stdin().lock())
+	}
+    }
+
+    // Token source shared by Scanner and interactive::Interactive.
+    // Implementors only provide token() and the label used in error messages.
+    #[allow(dead_code)]
+    pub trait Tokenizer {
+	// None at the end of input.
+	fn token(&mut self) -> Option<&str>;
+
+	// Name shown in error messages, set by input!.
+	fn set_label(&mut self, label: &'static str);
+
+	fn label(&self) -> &'static str;
+
+	fn read<T: FromStr>(&mut self) -> T {
+	    let label = self.label();
+	    let what = || {
+		let name = std::any::type_name::<T>();
+		if label.is_empty() { name.to_string() } else { format!("{}: {}", label, name) }
+	    };
+	    match self.token() {
+		Some(s) => match s.parse() {
+		    Ok(x) => x,
+		    Err(_) => panic!("ERROR: cannot parse {:?} as {}", s, what()),
+		},
+		None => panic!("ERROR: unexpected end of input while reading {}", what()),
+	    }
+	}
+
+	fn read_vec<T: FromStr>(&mut self, n: usize) -> Vec<T> {
+	    (0..n).map(|_| self.read()).collect()
+	}
+
+	fn read2<T: FromStr, U: FromStr>(&mut self) -> (T, U) {
+	    let a = self.read();
+	    (a, self.read())
+	}
+
+	fn read3<T: FromStr, U: FromStr, V: FromStr>(&mut self) -> (T, U, V) {
+	    let a = self.read();
+	    let b = self.read();
+	    (a, b, self.read())
+	}
+
+	fn chars(&mut self) -> Vec<char> {
+	    self.read::<String>().chars().collect()
+	}
+
+	fn bytes(&mut self) -> Vec<u8> {
+	    self.read::<String>().into_bytes()
+	}
+
+	// 1-based id to 0-based index.
+	fn usize1(&mut self) -> usize {
+	    let x = self.read::<usize>();
+	    assert!(x > 0, "ERROR: usize1 read 0");
+	    x - 1
+	}
+
+	fn usize1_vec(&mut self, n: usize) -> Vec<usize> {
+	    (0..n).map(|_| self.usize1()).collect()
+	}
+    }
+
+    impl<R: BufRead> Scanner<R> {
+	pub fn new(reader: R) -> Self {
+	    Self { reader, buf: Vec::new(), pos: 0, label: "" }
+	}
+    }
+
+    impl<R: BufRead> Tokenizer for Scanner<R> {
+	fn token(&mut self) -> Option<&str> {
+	    loop {
+		while self.pos < self.buf.len() && self.buf[self.pos].is_ascii_whitespace() {
+		    self.pos += 1;
+		}
+		if self.pos < self.buf.len() { break; }
+		self.buf.clear();
+		self.pos = 0;
+		if self.reader.read_until(b'\n', &mut self.buf).expect("ERROR: failed to read input") == 0 {
+		    return None;
+		}
+	    }
+	    let start = self.pos;
+	    while self.pos < self.buf.len() && !self.buf[self.pos].is_ascii_whitespace() {
+		self.pos += 1;
+	    }
+	    Some(std::str::from_utf8(&self.buf[start..self.pos]).expect("ERROR: input is not UTF-8"))
+	}
+
+	fn set_label(&mut self, label: &'static str) {
+	    self.label = label;
+	}
+
+	fn label(&self) -> &'static str {
+	    self.label
+	}
+    }
+
+    thread_local! {
+	static STDIN: RefCell<Scanner<StdinLock<'static>>> = RefCell::new(Scanner::stdin());
+    }
+
+    // Scanner used by input! without `from`.
+    // It keeps stdin locked, so don't mix it with another Scanner::stdin().
+    pub fn with_stdin<T, F: FnOnce(&mut Scanner<StdinLock<'static>>) -> T>(f: F) -> T {
+	STDIN.with(|sc| f(&mut sc.borrow_mut()))
+    }
+
+    // input!{ n: usize, a: [i64; n], edges: [(usize1, usize1, i64); m], s: chars }
+    // input!{ from sc, mut h: usize, w: usize, g: [bytes; h] }  (sc: any Tokenizer)
+    // Each type is one token tree: a FromStr type, usize1, chars, bytes,
+    // [T; len] or (T, U, ..).
+    #[allow(unused_macros)]
+    macro_rules! input {
+	(from $sc:expr, $($rest:tt)*) => {
+	    input_inner!{ ($sc) $($rest)* }
+	};
+	($($rest:tt)*) => {
+	    input_inner!{ () $($rest)* }
+	};
+    }
+
+    #[allow(unused_macros)]
+    macro_rules! input_inner {
+	(@read () $var:ident $t:tt) => {
+	    $crate::input::with_stdin(|sc| {
+		use $crate::input::Tokenizer as _;
+		sc.set_label(stringify!($var));
+		read_value!(sc, $t)
+	    })
+	};
+	(@read ($sc:expr) $var:ident $t:tt) => {{
+	    #[allow(unused_imports)]
+	    use $crate::input::Tokenizer as _;
+	    $sc.set_label(stringify!($var));
+	    read_value!($sc, $t)
+	}};
+	(($($sc:expr)?) $(,)?) => {};
+	(($($sc:expr)?) mut $var:ident : $t:tt $(, $($rest:tt)*)?) => {
+	    let mut $var = input_inner!(@read ($($sc)?) $var $t);
+	    input_inner!{ ($($sc)?) $($($rest)*)? }
+	};
+	(($($sc:expr)?) $var:ident : $t:tt $(, $($rest:tt)*)?) => {
+	    let $var = input_inner!(@read ($($sc)?) $var $t);
+	    input_inner!{ ($($sc)?) $($($rest)*)? }
+	};
+    }
+
+    #[allow(unused_macros)]
+    macro_rules! read_value {
+	($sc:expr, [$t:tt; $n:expr]) => {
+	    (0..$n).map(|_| read_value!($sc, $t)).collect::<Vec<_>>()
+	};
+	($sc:expr, ($($t:tt),* $(,)?)) => {
+	    ($(read_value!($sc, $t),)*)
+	};
+	($sc:expr, usize1) => { $sc.usize1() };
+	($sc:expr, chars) => { $sc.chars() };
+	($sc:expr, bytes) => { $sc.bytes() };
+	($sc:expr, $t:ty) => { $sc.read::<$t>() };
+    }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+
+	#[test]
+	fn tokens_across_lines() {
+	    let mut sc = Scanner::new(&b"3\n1 2\n  3\n\nabc 4 5\n"[..]);
+	    assert_eq!(sc.read::<usize>(), 3);
+	    assert_eq!(sc.read_vec::<i64>(3), vec![1, 2, 3]);
+	    assert_eq!(sc.chars(), vec!['a', 'b', 'c']);
+	    assert_eq!(sc.usize1(), 3);
+	    assert_eq!(sc.read::<u8>(), 5);
+	    assert_eq!(sc.token(), None);
+	}
+
+	#[test]
+	fn input_macro() {
+	    let mut sc = Scanner::new(&b"2 3\n1 2 5\n2 3 -1\nxy\n1 2 3 4 5 6"[..]);
+	    input!{ from sc, n: usize, m: usize, edges: [(usize1, usize1, i64); n], s: bytes, mut a: [[u8; m]; n] }
+	    a[1][2] += 1;
+	    assert_eq!(edges, vec![(0, 1, 5), (1, 2, -1)]);
+	    assert_eq!(s, b"xy".to_vec());
+	    assert_eq!(a, vec![vec![1, 2, 3], vec![4, 5, 7]]);
+	}
+
+	#[test]
+	#[should_panic(expected = "unexpected end of input while reading a")]
+	fn input_macro_eof() {
+	    let mut sc = Scanner::new(&b"3 1 2"[..]);
+	    input!{ from sc, n: usize, a: [i32; n] }
+	    assert_eq!(a.len(), n);
+	}
+    }
+}
@@ -0,0 +1,169 @@
+pub mod interactive {
+    use std::io::{BufRead, BufWriter, StdinLock, StdoutLock, Write};
+    use crate::input::{Scanner, Tokenizer};
+
+    // Reads tokens like input::Scanner, and flushes everything written so far
+    // before each read so that the judge sees the query before we wait for the reply.
+    //   let mut io = Interactive::stdio();
+    //   outln!(io, "? {}", x);
+    //   input!{ from io, r: i64 }
+    pub struct Interactive<R, W: Write> {
+	sc: Scanner<R>,
+	w: BufWriter<W>,
+    }
+
+    impl Interactive<StdinLock<'static>, StdoutLock<'static>> {
+	pub fn stdio() -> Self {
+	    Self::new(std::io::stdin().lock(), std::io::stdout().lock())
+	}
+    }
+
+    impl<R: BufRead, W: Write> Interactive<R, W> {
+	pub fn new(reader: R, writer: W) -> Self {
+	    Self { sc: Scanner::new(reader), w: BufWriter::new(writer) }
+	}
+    }
+
+    impl<R: BufRead, W: Write> Tokenizer for Interactive<R, W> {
+	fn token(&mut self) -> Option<&str> {
+	    self.w.flush().expect("ERROR: failed to write output");
+	    self.sc.token()
+	}
+
+	fn set_label(&mut self, label: &'static str) {
+	    self.sc.set_label(label);
+	}
+
+	fn label(&self) -> &'static str {
+	    self.sc.label()
+	}
+    }
+
+    impl<R: BufRead, W: Write> Write for Interactive<R, W> {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+	    self.w.write(buf)
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+	    self.w.flush()
+	}
+    }
+
+    // Offline stand-in for an interactive judge.
+    // The judge runs on its own thread and talks to the solution over in-memory pipes:
+    //   let verdict = local_judge::run(|io| { ... judge ... }, |io| solve(io));
+    // where solve is generic over `I: Tokenizer + Write`.
+    pub mod local_judge {
+	use std::collections::VecDeque;
+	use std::io::{BufReader, Read, Write};
+	use std::sync::{Arc, Condvar, Mutex};
+	use super::Interactive;
+
+	#[derive(Default)]
+	struct Pipe {
+	    buf: Mutex<(VecDeque<u8>, bool)>, // (data, closed)
+	    cond: Condvar,
+	}
+
+	pub struct PipeReader(Arc<Pipe>);
+	pub struct PipeWriter(Arc<Pipe>);
+
+	pub fn pipe() -> (PipeWriter, PipeReader) {
+	    let p = Arc::new(Pipe::default());
+	    (PipeWriter(p.clone()), PipeReader(p))
+	}
+
+	// Blocks until some data arrives; returns 0 once the writer is dropped.
+	impl Read for PipeReader {
+	    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+		let mut g = self.0.buf.lock().unwrap();
+		while g.0.is_empty() && !g.1 {
+		    g = self.0.cond.wait(g).unwrap();
+		}
+		let n = out.len().min(g.0.len());
+		for (o, b) in out.iter_mut().zip(g.0.drain(..n)) {
+		    *o = b;
+		}
+		Ok(n)
+	    }
+	}
+
+	impl Write for PipeWriter {
+	    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+		self.0.buf.lock().unwrap().0.extend(data);
+		self.0.cond.notify_all();
+		Ok(data.len())
+	    }
+
+	    fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	    }
+	}
+
+	impl Drop for PipeWriter {
+	    fn drop(&mut self) {
+		self.0.buf.lock().unwrap().1 = true;
+		self.0.cond.notify_all();
+	    }
+	}
+
+	pub type PipeIo = Interactive<BufReader<PipeReader>, PipeWriter>;
+
+	// Returns what the judge returns.
+	// Each side's output is closed when its closure returns, so the other side reads EOF.
+	pub fn run<T, J, S>(judge: J, solution: S) -> T
+	where T: Send,
+	      J: FnOnce(&mut PipeIo) -> T + Send,
+	      S: FnOnce(&mut PipeIo),
+	{
+	    let (to_judge, from_solution) = pipe();
+	    let (to_solution, from_judge) = pipe();
+	    std::thread::scope(|s| {
+		let handle = s.spawn(move || {
+		    let mut io = Interactive::new(BufReader::new(from_solution), to_solution);
+		    judge(&mut io)
+		});
+		{
+		    let mut io = Interactive::new(BufReader::new(from_judge), to_judge);
+		    solution(&mut io);
+		}
+		handle.join().expect("ERROR: judge panicked")
+	    })
+	}
+    }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+
+	fn guess<I: Tokenizer + Write>(io: &mut I) {
+	    input!{ from io, n: u64 }
+	    let (mut lo, mut hi) = (1, n + 1);
+	    while hi - lo > 1 {
+		let mid = (lo + hi) / 2;
+		outln!(io, "? {}", mid);
+		input!{ from io, r: String }
+		if r == "<" { hi = mid; } else { lo = mid; }
+	    }
+	    outln!(io, "! {}", lo);
+	}
+
+	#[test]
+	fn binary_search_judge() {
+	    for secret in 1..=20 {
+		let (answer, queries) = local_judge::run(|io| {
+		    outln!(io, "20");
+		    let mut queries = 0;
+		    loop {
+			let (c, x): (String, u64) = io.read2();
+			if c == "!" { return (x, queries); }
+			queries += 1;
+			outln!(io, "{}", if x > secret { "<" } else { ">=" });
+		    }
+		}, guess);
+		assert_eq!(answer, secret);
+		assert!(queries <= 5);
+	    }
+	}
+    }
+} // mod interactive;
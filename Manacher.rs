@@ -1,4 +1,4 @@
-mod manacher {
+pub mod manacher {
     #[derive(Debug, Default)]
     pub struct Manacher {
 	rad: Vec<usize>,
@@ -31,4 +31,23 @@ mod manacher {
 	    r-l <= self.rad[l+r-1]
 	}
     }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+
+	#[test]
+	fn all_substrings() {
+	    for mask in 0..1 << 8 {
+		let s: Vec<u8> = (0..8).map(|i| b'a' + (mask >> i & 1) as u8).collect();
+		let m = Manacher::new(&s);
+		for l in 0..s.len() {
+		    for r in l + 1..=s.len() {
+			let p = s[l..r].iter().eq(s[l..r].iter().rev());
+			assert_eq!(m.ok(l..r), p, "{:?} {}..{}", s, l, r);
+		    }
+		}
+	    }
+	}
+    }
 } // mod manacher;
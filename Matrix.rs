@@ -1,4 +1,4 @@
-mod matrix {
+pub mod matrix {
     use std::ops::*;
     use std::fmt::Debug;
 
@@ -11,6 +11,34 @@ mod matrix {
     pub trait ElemTrait: Debug + Default + Copy + Clone + Add<Output=Self> + AddAssign + Sub<Output=Self> + SubAssign + Mul<Output=Self> + MulAssign {}
     impl<T: Debug + Default + Copy + Clone + Add<Output=Self> + AddAssign + Sub<Output=Self> + SubAssign + Mul<Output=Self> + MulAssign> ElemTrait for T {}
 
+    // Elements with a multiplicative unit, for identity matrices.
+    pub trait OneTrait {
+        fn one() -> Self;
+    }
+
+    macro_rules! impl_one {
+        ($($t:ty, $one:expr),*) => {
+            $(
+                impl OneTrait for $t {
+                    fn one() -> Self { $one }
+                }
+            )*
+        };
+    }
+    impl_one!(i32, 1, i64, 1, i128, 1, u32, 1, u64, 1, usize, 1, f32, 1.0, f64, 1.0);
+
+    impl<const M: u64> OneTrait for crate::mod_int::ModInt<M> {
+        fn one() -> Self { Self::ONE }
+    }
+
+    impl<const M: u64> OneTrait for crate::mod_int::ModInt64<M> {
+        fn one() -> Self { Self::ONE }
+    }
+
+    impl OneTrait for crate::mod_int::DynModInt {
+        fn one() -> Self { Self::ONE }
+    }
+
     macro_rules! gen_bin_op {
         ($( (($($types:tt)*), $type:ty, $op_trait:ident, $op:ident, $op_assign:ident) ),*) => {
             $(
@@ -53,8 +81,8 @@ mod matrix {
     //////////////////////////////////////////////////
     mod array {
         use std::ops::*;
-        use matrix::Array;
-        use matrix::ElemTrait;
+        use crate::matrix::Array;
+        use crate::matrix::ElemTrait;
 
         impl<T: ElemTrait, const N: usize> Default for Array<T, N> {
             fn default() -> Self {
@@ -114,8 +142,8 @@ mod matrix {
     //////////////////////////////////////////////////
     mod matrix {
         use std::ops::*;
-        use matrix::Matrix;
-        use matrix::ElemTrait;
+        use crate::matrix::Matrix;
+        use crate::matrix::ElemTrait;
 
         impl<T: Default + Copy, const N: usize> Default for Matrix<T, N> {
             fn default() -> Self {
@@ -207,9 +235,204 @@ mod matrix {
         }
     }
 
+    //////////////////////////////////////////////////
+    // DMatrix: rows x cols, sized at runtime
+    //////////////////////////////////////////////////
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct DMatrix<T> {
+        rows: usize,
+        cols: usize,
+        a: Vec<T>,
+    }
+
+    mod dmatrix {
+        use std::ops::*;
+        use crate::matrix::DMatrix;
+        use crate::matrix::{ElemTrait, OneTrait};
+
+        impl<T: ElemTrait> DMatrix<T> {
+            pub fn new(rows: usize, cols: usize) -> Self {
+                Self { rows, cols, a: vec![T::default(); rows * cols] }
+            }
+
+            pub fn identity(n: usize) -> Self where T: OneTrait {
+                let mut m = Self::new(n, n);
+                for i in 0..n { m[i][i] = T::one(); }
+                m
+            }
+
+            pub fn rows(&self) -> usize {
+                self.rows
+            }
+
+            pub fn cols(&self) -> usize {
+                self.cols
+            }
+
+            pub fn transpose(&self) -> Self {
+                let mut t = Self::new(self.cols, self.rows);
+                for i in 0..self.rows {
+                    for j in 0..self.cols {
+                        t[j][i] = self[i][j];
+                    }
+                }
+                t
+            }
+
+            pub fn pow(&self, mut e: u64) -> Self where T: OneTrait {
+                assert!(self.rows == self.cols, "ERROR: DMatrix pow of a {}x{} matrix", self.rows, self.cols);
+                let mut r = Self::identity(self.rows);
+                let mut x = self.clone();
+                while e > 0 {
+                    if e & 1 == 1 { r *= &x; }
+                    x = &x * &x;
+                    e >>= 1;
+                }
+                r
+            }
+        }
+
+        impl<T: ElemTrait> From<Vec<Vec<T>>> for DMatrix<T> {
+            fn from(v: Vec<Vec<T>>) -> Self {
+                let rows = v.len();
+                let cols = if rows == 0 { 0 } else { v[0].len() };
+                assert!(v.iter().all(|r| r.len() == cols), "ERROR: DMatrix rows of different lengths");
+                Self { rows, cols, a: v.concat() }
+            }
+        }
+
+        impl<T> Index<usize> for DMatrix<T> {
+            type Output = [T];
+            fn index(&self, i: usize) -> &Self::Output {
+                assert!(i < self.rows, "ERROR: DMatrix index out of bounds rows={}, index={}", self.rows, i);
+                &self.a[i * self.cols .. (i + 1) * self.cols]
+            }
+        }
+
+        impl<T> IndexMut<usize> for DMatrix<T> {
+            fn index_mut(&mut self, i: usize) -> &mut Self::Output {
+                assert!(i < self.rows, "ERROR: DMatrix index out of bounds rows={}, index={}", self.rows, i);
+                &mut self.a[i * self.cols .. (i + 1) * self.cols]
+            }
+        }
+
+        impl<T: ElemTrait> AddAssign<&DMatrix<T>> for DMatrix<T> {
+            fn add_assign(&mut self, other: &DMatrix<T>) {
+                assert!((self.rows, self.cols) == (other.rows, other.cols), "ERROR: DMatrix sizes differ");
+                for i in 0..self.a.len() {
+                    self.a[i] += other.a[i];
+                }
+            }
+        }
+
+        impl<T: ElemTrait> AddAssign for DMatrix<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self += &other;
+            }
+        }
+
+        impl<T: ElemTrait> SubAssign<&DMatrix<T>> for DMatrix<T> {
+            fn sub_assign(&mut self, other: &DMatrix<T>) {
+                assert!((self.rows, self.cols) == (other.rows, other.cols), "ERROR: DMatrix sizes differ");
+                for i in 0..self.a.len() {
+                    self.a[i] -= other.a[i];
+                }
+            }
+        }
+
+        impl<T: ElemTrait> SubAssign for DMatrix<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self -= &other;
+            }
+        }
+
+        impl<T: ElemTrait> MulAssign<&DMatrix<T>> for DMatrix<T> {
+            fn mul_assign(&mut self, other: &Self) {
+                assert!(self.cols == other.rows, "ERROR: DMatrix {}x{} times {}x{}", self.rows, self.cols, other.rows, other.cols);
+                let mut d = DMatrix::new(self.rows, other.cols);
+                for i in 0..self.rows {
+                    for j in 0..self.cols {
+                        for k in 0..other.cols {
+                            d[i][k] += self[i][j] * other[j][k];
+                        }
+                    }
+                }
+                *self = d
+            }
+        }
+
+        impl<T: ElemTrait> MulAssign for DMatrix<T> {
+            fn mul_assign(&mut self, other: Self) {
+                *self *= &other;
+            }
+        }
+
+        gen_bin_op!{
+            ((T: ElemTrait), DMatrix<T>, Add, add, add_assign),
+            ((T: ElemTrait), DMatrix<T>, Sub, sub, sub_assign),
+            ((T: ElemTrait), DMatrix<T>, Mul, mul, mul_assign)}
+
+        // self * v for a column vector v
+        impl<T: ElemTrait> Mul<&[T]> for &DMatrix<T> {
+            type Output = Vec<T>;
+            fn mul(self, v: &[T]) -> Self::Output {
+                assert!(self.cols == v.len(), "ERROR: DMatrix {}x{} times vector of length {}", self.rows, self.cols, v.len());
+                (0..self.rows).map(|i| {
+                    let mut s = T::default();
+                    for j in 0..self.cols { s += self[i][j] * v[j]; }
+                    s
+                }).collect()
+            }
+        }
+
+        impl<T: ElemTrait> Mul<Vec<T>> for &DMatrix<T> {
+            type Output = Vec<T>;
+            fn mul(self, v: Vec<T>) -> Self::Output {
+                self * &v[..]
+            }
+        }
+
+        impl<T: ElemTrait> Mul<Vec<T>> for DMatrix<T> {
+            type Output = Vec<T>;
+            fn mul(self, v: Vec<T>) -> Self::Output {
+                &self * &v[..]
+            }
+        }
+    }
+
     pub mod gaussian_elimination {
         use std::ops::Div;
-        use matrix::{ElemTrait, Array, Matrix};
+        use crate::matrix::{Array, ElemTrait, Matrix, OneTrait};
+        use crate::mod_int::{DynModInt, ModInt, ModInt64};
+
+        // Elements we can divide by. Pivots are chosen by the largest weight,
+        // so floats get partial pivoting and exact fields take the first non-zero entry.
+        // Floats count as zero below a fixed absolute eps (1e-9 for f64, 1e-5 for f32),
+        // so scale the matrix to entries around 1 before eliminating.
+        pub trait FieldTrait: ElemTrait + OneTrait + PartialEq + Div<Output=Self> {
+            fn is_zero(&self) -> bool {
+                *self == Self::default()
+            }
+            fn weight(&self) -> f64 {
+                0.0
+            }
+        }
+
+        macro_rules! impl_float_field {
+            ($($t:ty, $eps:expr),*) => {
+                $(
+                    impl FieldTrait for $t {
+                        fn is_zero(&self) -> bool { self.abs() < $eps }
+                        fn weight(&self) -> f64 { self.abs() as f64 }
+                    }
+                )*
+            };
+        }
+        impl_float_field!(f64, 1e-9, f32, 1e-5);
+
+        impl<const M: u64> FieldTrait for ModInt<M> {}
+        impl<const M: u64> FieldTrait for ModInt64<M> {}
+        impl FieldTrait for DynModInt {}
 
         #[derive(Debug, Clone)]
         pub enum Operation<T> {
@@ -217,23 +440,26 @@ mod matrix {
             ADD(usize, usize, T), // m[i] += m[j] * rate;
         }
 
+        // After new(), row i < rank has its pivot at column pivots[i],
+        // and every other row is zero in that column.
         #[derive(Debug, Clone)]
         pub struct GaussianElimination<T, const N: usize> {
             a: Matrix<T, N>,
             v: Vec<Operation<T>>,
+            pivots: Vec<usize>,
         }
 
-        impl<T: ElemTrait + PartialEq + Div<Output=T>, const N: usize>  GaussianElimination<T, N> {
+        impl<T: FieldTrait, const N: usize>  GaussianElimination<T, N> {
             pub fn new(mut mat: Matrix<T, N>) -> Self {
                 let mut r = 0;
                 let mut c = 0;
                 let mut v = Vec::new();
+                let mut pivots = Vec::new();
                 while r < N && c < N {
                     let mut pivot = usize::MAX;
                     for i in r..N {
-                        if mat[i][c] != T::default() {
+                        if !mat[i][c].is_zero() && (pivot == usize::MAX || mat[i][c].weight() > mat[pivot][c].weight()) {
                             pivot = i;
-                            break;
                         }
                     }
                     if pivot == usize::MAX {
@@ -249,50 +475,291 @@ mod matrix {
                         }
                     }
                     for i in 0..N {
-                        if i != r && mat[i][c] != T::default() {
+                        if i != r && !mat[i][c].is_zero() {
                             let rate = T::default() - mat[i][c] / mat[r][c];
                             v.push(Operation::ADD(i, r, rate));
                             for j in c..N {
                                 let tmp = mat[r][j] * rate;
                                 mat[i][j] += tmp;
                             }
+                            mat[i][c] = T::default();
                         }
                     }
+                    pivots.push(c);
                     r += 1;
                     c += 1;
                 }
-                GaussianElimination { a: mat, v }
+                GaussianElimination { a: mat, v, pivots }
+            }
+
+            pub fn rank(&self) -> usize {
+                self.pivots.len()
+            }
+
+            pub fn det(&self) -> T {
+                if self.rank() < N {
+                    return T::default();
+                }
+                let mut d = T::one();
+                for i in 0..N {
+                    d *= self.a[i][i];
+                }
+                for op in self.v.iter() {
+                    if let Operation::SWAP(_, _) = op {
+                        d = T::default() - d;
+                    }
+                }
+                d
+            }
+
+            // Replays the recorded row operations onto b.
+            pub fn apply(&self, b: &mut Array<T, N>) {
+                for op in self.v.iter() {
+                    match *op {
+                        Operation::SWAP(i, j) => b.0.swap(i, j),
+                        Operation::ADD(i, j, rate) => {
+                            let tmp = b[j] * rate;
+                            b[i] += tmp;
+                        }
+                    }
+                }
+            }
+
+            pub fn inverse(&self) -> Option<Matrix<T, N>> {
+                if self.rank() < N {
+                    return None;
+                }
+                let mut inv = Matrix::<T, N>::default();
+                for j in 0..N {
+                    let mut e = Array::<T, N>::default();
+                    e[j] = T::one();
+                    self.apply(&mut e);
+                    for i in 0..N {
+                        inv[i][j] = e[i] / self.a[i][i];
+                    }
+                }
+                Some(inv)
+            }
+
+            // Solutions of mat * x = b are particular + any combination of the kernel basis.
+            pub fn solve(&self, b: &Array<T, N>) -> Option<(Array<T, N>, Vec<Array<T, N>>)> {
+                let mut b = b.clone();
+                self.apply(&mut b);
+                if (self.rank()..N).any(|i| !b[i].is_zero()) {
+                    return None;
+                }
+                let mut x = Array::<T, N>::default();
+                let mut is_pivot = [false; N];
+                for (r, &c) in self.pivots.iter().enumerate() {
+                    x[c] = b[r] / self.a[r][c];
+                    is_pivot[c] = true;
+                }
+                let mut kernel = Vec::new();
+                for f in (0..N).filter(|&f| !is_pivot[f]) {
+                    let mut k = Array::<T, N>::default();
+                    k[f] = T::one();
+                    for (r, &c) in self.pivots.iter().enumerate() {
+                        k[c] = T::default() - self.a[r][f] / self.a[r][c];
+                    }
+                    kernel.push(k);
+                }
+                Some((x, kernel))
             }
         } // impl;
     } // mod gaussian_elimination;
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::stress::XorShift;
+
+        #[test]
+        fn array_ops() {
+            let mut a = Array::<i32, 4>::default();
+            a[0] = 1;
+            a[3] = 2;
+            let mut b = a + a;
+            b += a;
+            b -= &a;
+            assert_eq!(b.0, [2, 0, 0, 4]);
+            assert_eq!((b - a).0, [1, 0, 0, 2]);
+        }
+
+        #[test]
+        fn matrix_ops() {
+            let mut a = Matrix::<i64, 3>::default();
+            let mut b = Matrix::<i64, 3>::default();
+            for i in 0..3 {
+                for j in 0..3 {
+                    a[i][j] = (i * 3 + j) as i64;
+                    b[i][j] = (i as i64 - j as i64) * 2;
+                }
+            }
+            let c = &a * &b;
+            let s = a.clone() + &b;
+            let d = &s - b.clone();
+            for i in 0..3 {
+                for j in 0..3 {
+                    assert_eq!(c[i][j], (0..3).map(|k| a[i][k] * b[k][j]).sum::<i64>());
+                    assert_eq!(s[i][j], a[i][j] + b[i][j]);
+                    assert_eq!(d[i][j], a[i][j]);
+                }
+            }
+            let mut v = Array::<i64, 3>::default();
+            v[0] = 1;
+            v[2] = -1;
+            let w = a.clone() * v;
+            for i in 0..3 {
+                assert_eq!(w[i], a[i][0] - a[i][2]);
+            }
+        }
+
+        #[test]
+        fn dmatrix_ops() {
+            let a = DMatrix::from(vec![vec![1i64, 2, 3], vec![4, 5, 6]]);
+            let b = a.transpose();
+            assert_eq!((b.rows(), b.cols()), (3, 2));
+            assert_eq!(b, DMatrix::from(vec![vec![1, 4], vec![2, 5], vec![3, 6]]));
+            let c = &a * &b;
+            assert_eq!(c, DMatrix::from(vec![vec![14, 32], vec![32, 77]]));
+            assert_eq!(&c - &c + &c, c);
+            assert_eq!(&a * &[1, 0, -1][..], vec![-2, -2]);
+            assert_eq!(a.clone() * vec![0, 1, 1], vec![5, 11]);
+            assert_eq!(&DMatrix::identity(2) * &a, a);
+
+            let r = DMatrix::from(vec![vec![0.0, -1.0], vec![1.0, 0.0]]);
+            assert_eq!(r.pow(4), DMatrix::<f64>::identity(2));
+            assert_eq!(r.pow(2), DMatrix::from(vec![vec![-1.0, 0.0], vec![0.0, -1.0]]));
+            assert_eq!(&r * vec![2.0, 3.0], vec![-3.0, 2.0]);
+            assert_eq!(DMatrix::from(vec![vec![2i32]]).pow(10)[0][0], 1024);
+
+            // fibonacci
+            type Mint = crate::mod_int::ModInt1000000007;
+            let f = DMatrix::from(vec![vec![Mint::new(1), Mint::new(1)], vec![Mint::new(1), Mint::new(0)]]);
+            assert_eq!(f.pow(90)[0][1], Mint::new(2880067194370816120));
+            assert_eq!(f.pow(0), DMatrix::identity(2));
+            let mut g = DMatrix::<Mint>::identity(2);
+            for e in 0..20 {
+                assert_eq!(f.pow(e), g);
+                g *= f.clone();
+            }
+        }
+
+        #[test]
+        fn gaussian_elimination() {
+            use crate::matrix::gaussian_elimination::GaussianElimination;
+            use crate::mod_int::ModInt998244353;
+            type Mint = ModInt998244353;
+            let mut rng = XorShift::new(1);
+            for it in 0..300 {
+                // low rank matrices are common with entries in 0..3
+                let mut a = Matrix::<Mint, 4>::default();
+                let mut b = Array::<Mint, 4>::default();
+                for i in 0..4 {
+                    for j in 0..4 {
+                        let x = rng.next_u64();
+                        a[i][j] = Mint::new(if it % 2 == 0 { x % 3 } else { x });
+                    }
+                    b[i] = Mint::new(rng.next_u64() % 5);
+                }
+                let g = GaussianElimination::new(a.clone());
+
+                // cofactor expansion along the first row
+                fn det(a: &Vec<Vec<Mint>>) -> Mint {
+                    if a.is_empty() {
+                        return Mint::ONE;
+                    }
+                    let mut d = Mint::ZERO;
+                    for j in 0..a.len() {
+                        let minor = a[1..].iter().map(|r| [&r[..j], &r[j + 1..]].concat()).collect();
+                        let s = a[0][j] * det(&minor);
+                        if j % 2 == 0 { d += s } else { d -= s }
+                    }
+                    d
+                }
+                let v = (0..4).map(|i| (0..4).map(|j| a[i][j]).collect()).collect();
+                assert_eq!(g.det(), det(&v));
+                assert_eq!(g.rank() == 4, g.det() != Mint::ZERO);
+
+                match g.inverse() {
+                    Some(inv) => {
+                        let p = &a * &inv;
+                        for i in 0..4 {
+                            for j in 0..4 {
+                                assert_eq!(p[i][j], Mint::new((i == j) as u64));
+                            }
+                        }
+                    }
+                    None => assert!(g.rank() < 4),
+                }
+
+                let zero = Array::<Mint, 4>::default();
+                match g.solve(&b) {
+                    Some((p, kernel)) => {
+                        assert_eq!(kernel.len(), 4 - g.rank());
+                        assert_eq!((a.clone() * p).0, b.0);
+                        for k in kernel.iter() {
+                            assert_eq!((a.clone() * k.clone()).0, zero.0);
+                        }
+                    }
+                    // then b is outside the column space, so rank [a | b] > rank a
+                    None => {
+                        let mut ab = Matrix::<Mint, 5>::default();
+                        for i in 0..4 {
+                            for j in 0..4 { ab[i][j] = a[i][j]; }
+                            ab[i][4] = b[i];
+                        }
+                        assert_eq!(GaussianElimination::new(ab).rank(), g.rank() + 1);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn gaussian_elimination_f64() {
+            use crate::matrix::gaussian_elimination::GaussianElimination;
+            let mut a = Matrix::<f64, 3>::default();
+            let rows = [[1e-12, 2.0, 1.0], [3.0, 1.0, -1.0], [2.0, -1.0, 4.0]];
+            for i in 0..3 {
+                for j in 0..3 { a[i][j] = rows[i][j]; }
+            }
+            let g = GaussianElimination::new(a.clone());
+            assert_eq!(g.rank(), 3);
+            assert!((g.det() - (-33.0)).abs() < 1e-9);
+            let inv = g.inverse().unwrap();
+            let p = &a * &inv;
+            for i in 0..3 {
+                for j in 0..3 {
+                    assert!((p[i][j] - (i == j) as i32 as f64).abs() < 1e-9);
+                }
+            }
+
+            // singular: third row = first + second
+            for j in 0..3 { a[2][j] = a[0][j] + a[1][j]; }
+            let g = GaussianElimination::new(a.clone());
+            assert_eq!(g.rank(), 2);
+            assert_eq!(g.det(), 0.0);
+            assert!(g.inverse().is_none());
+            let mut b = Array::<f64, 3>::default();
+            b[0] = 1.0;
+            b[1] = 2.0;
+            b[2] = 3.0;
+            let (x, kernel) = g.solve(&b).unwrap();
+            assert_eq!(kernel.len(), 1);
+            let (ax, ak) = (a.clone() * x, a.clone() * kernel[0].clone());
+            for i in 0..3 {
+                assert!((ax[i] - b[i]).abs() < 1e-9);
+                assert!(ak[i].abs() < 1e-9);
+            }
+            b[2] = 4.0;
+            assert!(g.solve(&b).is_none());
+        }
+
+        #[test]
+        #[should_panic]
+        fn dmatrix_size_mismatch() {
+            let a = DMatrix::<i64>::new(2, 3);
+            let _ = &a * &a;
+        }
+    }
 } // mod matrix;
-use matrix::*;
-
-fn main() {
-    let mut a = Array::<i32, 4>::default();
-    a[0] = 1;
-    let mut b = a + a;
-    b += a;
-    println!("{:?}", b);
-    println!("{:?}", b[0]);
-    println!("{:?}", a);
-    let mut a = Array::<i32, 4>::default();
-    a[0] = 1;
-    let mut b = a + a;
-    b += a;
-
-    let mut a = Matrix::<i32, 3>::default();
-    a += a.clone();
-    a[0][0] = 1;
-    a[1][1] = 2;
-    a[2][2] = 3;
-    a[0][1] = 4;
-    a[1][2] = 5;
-    a = &a + &a;
-    a = a.clone() + a.clone();
-    a = &a + (&a + &a) + &a;
-    println!("{:?}", a);
-
-    let ge = matrix::gaussian_elimination::GaussianElimination::new(a.clone());
-    println!("{:?}", ge);
-}
@@ -1,4 +1,4 @@
-mod merge {
+pub mod merge {
     use std::cmp::Ordering;
 
     pub trait Merge<T, Output> {
@@ -37,4 +37,21 @@ mod merge {
 	    (&self).merge_by(&other, comp)
 	}
     }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+
+	#[test]
+	fn merge_sorted() {
+	    let a = vec![1, 3, 3, 8];
+	    let b = vec![0, 3, 9];
+	    let mut c = [a.clone(), b.clone()].concat();
+	    c.sort();
+	    assert_eq!((&a).merge(&b), c);
+	    assert_eq!(a.clone().merge(vec![]), a);
+	    let desc = vec![8, 3, 1].merge_by(vec![9, 2], |x, y| y.cmp(x));
+	    assert_eq!(desc, vec![9, 8, 3, 2, 1]);
+	}
+    }
 } // mod merge;
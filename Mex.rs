@@ -1,14 +1,29 @@
-fn mex(a: &Vec<usize>) -> usize {
-    let mut c = vec![false; a.len()];
-    for &x in a {
-	if x < a.len() {
-	    c[x] = true;
+pub mod mex {
+    pub fn mex(a: &Vec<usize>) -> usize {
+	let mut c = vec![false; a.len()];
+	for &x in a {
+	    if x < a.len() {
+		c[x] = true;
+	    }
 	}
+	for i in 0..a.len() {
+	    if !c[i] {
+		return i;
+	    }
+	}
+	a.len()
     }
-    for i in 0..a.len() {
-	if !c[i] {
-	    return i;
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+
+	#[test]
+	fn small() {
+	    assert_eq!(mex(&vec![]), 0);
+	    assert_eq!(mex(&vec![1, 2]), 0);
+	    assert_eq!(mex(&vec![0, 5, 1, 1]), 2);
+	    assert_eq!(mex(&vec![2, 1, 0]), 3);
 	}
     }
-    a.len()
 }
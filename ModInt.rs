@@ -1,4 +1,6 @@
-mod mod_int {
+pub mod mod_int {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
     use std::ops::*;
 
     #[derive(Clone, Copy, Default, Debug, PartialEq)]
@@ -7,22 +9,52 @@ mod mod_int {
     pub type ModInt998244353 = ModInt::<998244353>;
     pub type ModInt1000000007 = ModInt::<1000000007>;
 
-    pub trait ModIntTrait: Copy + Default + Mul<Output=Self> + From<i64> {
+    pub trait ModIntTrait: Copy + Default + PartialEq + Add<Output=Self> + Sub<Output=Self> + Mul<Output=Self> + From<i64> + From<u64> {
 	const ZERO: Self;
 	const ONE: Self;
 
+	fn modulus() -> u64;
+	// Panics if self is not invertible; each type has checked_inv to test first.
 	fn inv(&self) -> Self;
+
+	fn pow(&self, mut y: u64) -> Self {
+	    let mut r = Self::ONE;
+	    let mut x = *self;
+	    while y > 0 {
+		if y & 1 == 1 { r = r * x; }
+		x = x * x;
+		y /= 2;
+	    }
+	    r
+	}
     }
 
     impl<const M: u64> ModIntTrait for ModInt<M> {
 	const ZERO : Self = ModInt::<M>::ZERO;
 	const ONE : Self = ModInt::<M>::ONE;
 
+	fn modulus() -> u64 {
+	    M
+	}
+
+	// Panics if not invertible; see checked_inv.
         fn inv(&self) -> Self {
-            self.pow(M - 2)
+	    self.checked_inv().unwrap_or_else(|| panic!("ERROR: ModInt {} is not invertible mod {}", self.0, M))
         }
     }
 
+    // x with a * x = 1 mod m by extended Euclid; None if gcd(a, m) != 1.
+    fn inv_gcd(a: i128, m: i128) -> Option<i128> {
+	let (mut a, mut b) = (a.rem_euclid(m), m);
+	let (mut x, mut y) = (1i128, 0i128);
+	while b != 0 {
+	    let t = a / b;
+	    (a, b) = (b, a - t * b);
+	    (x, y) = (y, x - t * y);
+	}
+	if a == 1 { Some(x.rem_euclid(m)) } else { None }
+    }
+
     impl<const M: u64> ModInt<M> {
 	pub const ZERO: Self = Self(0);
 	pub const ONE: Self = Self(1);
@@ -54,9 +86,213 @@ mod mod_int {
             }
             r
         }
+
+	// Works for any modulus, prime or not.
+	pub fn checked_inv(&self) -> Option<Self> {
+	    inv_gcd(self.0 as i128, M as i128).map(|x| Self(x as u32))
+	}
+    }
+
+    const fn pow_mod(mut a: u64, mut e: u64, m: u64) -> u64 {
+	let mut r = 1 % m;
+	a %= m;
+	while e > 0 {
+	    if e & 1 == 1 { r = (r as u128 * a as u128 % m as u128) as u64; }
+	    a = (a as u128 * a as u128 % m as u128) as u64;
+	    e >>= 1;
+	}
+	r
+    }
+
+    pub const fn is_prime(n: u64) -> bool {
+	let mut p = 2;
+	while p * p <= n {
+	    if n % p == 0 { return false; }
+	    p += 1;
+	}
+	n >= 2
+    }
+
+    // Smallest primitive root of a prime m; 0 if m is not prime.
+    pub const fn primitive_root(m: u64) -> u64 {
+	if !is_prime(m) { return 0; }
+	if m == 2 { return 1; }
+	let mut ps = [0u64; 64];
+	let mut k = 0;
+	let mut n = m - 1;
+	let mut p = 2;
+	while p * p <= n {
+	    if n % p == 0 {
+		ps[k] = p;
+		k += 1;
+		while n % p == 0 { n /= p; }
+	    }
+	    p += 1;
+	}
+	if n > 1 {
+	    ps[k] = n;
+	    k += 1;
+	}
+	let mut g = 2;
+	loop {
+	    let mut i = 0;
+	    while i < k && pow_mod(g, (m - 1) / ps[i], m) != 1 { i += 1; }
+	    if i == k { return g; }
+	    g += 1;
+	}
+    }
+
+    fn prime_factors(mut n: u64) -> Vec<u64> {
+	let mut ps = Vec::new();
+	let mut p = 2;
+	while p * p <= n {
+	    if n % p == 0 {
+		ps.push(p);
+		while n % p == 0 { n /= p; }
+	    }
+	    p += 1;
+	}
+	if n > 1 { ps.push(n); }
+	ps
+    }
+
+    // x = r_i mod m_i for all i, as (x mod lcm, lcm); panics if the lcm does not fit in i64.
+    pub fn crt(rm: &[(i64, i64)]) -> Option<(i64, i64)> {
+	let (mut r0, mut m0) = (0i128, 1i128);
+	for &(r1, m1) in rm {
+	    assert!(m1 >= 1, "ERROR: crt modulus {}", m1);
+	    let (r1, m1) = ((r1 as i128).rem_euclid(m1 as i128), m1 as i128);
+	    let (mut g, mut h) = (m0, m1);
+	    while h != 0 { (g, h) = (h, g % h); }
+	    if (r1 - r0) % g != 0 { return None; }
+	    let u = m1 / g;
+	    let x = (r1 - r0) / g % u * inv_gcd(m0 / g, u).unwrap() % u;
+	    r0 += x * m0;
+	    // m0, u < 2^63, so neither product overflows i128
+	    m0 *= u;
+	    assert!(m0 <= i64::MAX as i128, "ERROR: crt lcm {} does not fit in i64", m0);
+	    r0 = r0.rem_euclid(m0);
+	}
+	Some((r0 as i64, m0 as i64))
+    }
+
+    // Garner: t with x = t_0 + t_1 m_0 + t_2 m_0 m_1 + ..., 0 <= t_i < m_i.
+    fn garner_digits(rm: &[(u64, u64)]) -> Vec<u64> {
+	let mut t = Vec::with_capacity(rm.len());
+	for (i, &(r, m)) in rm.iter().enumerate() {
+	    let (mut x, mut p) = (0u128, 1u128);
+	    let m = m as u128;
+	    for j in 0..i {
+		x = (x + t[j] as u128 * p) % m;
+		p = p * rm[j].1 as u128 % m;
+	    }
+	    let inv = inv_gcd(p as i128, m as i128).expect("ERROR: garner moduli must be pairwise coprime") as u128;
+	    t.push(((r as u128 % m + m - x) % m * inv % m) as u64);
+	}
+	t
     }
 
-    impl<const M: u64> Add for ModInt<M> {
+    // The x < m_0 m_1 ... with x = r_i mod m_i, reduced mod `modulus`; the m_i must be pairwise coprime.
+    // let x = garner(&[(a.getu() as u64, 998244353), (b.getu() as u64, 167772161)], 1000000007);
+    pub fn garner(rm: &[(u64, u64)], modulus: u64) -> u64 {
+	let (mut x, mut p) = (0u128, 1u128);
+	let m = modulus as u128;
+	for (t, &(_, mi)) in garner_digits(rm).into_iter().zip(rm) {
+	    x = (x + t as u128 * p) % m;
+	    p = p * mi as u128 % m;
+	}
+	x as u64
+    }
+
+    // Exact x; the product of the m_i must fit in u128.
+    pub fn garner_u128(rm: &[(u64, u64)]) -> u128 {
+	let (mut x, mut p) = (0u128, 1u128);
+	for (i, t) in garner_digits(rm).into_iter().enumerate() {
+	    x += t as u128 * p;
+	    if i + 1 < rm.len() { p *= rm[i].1 as u128; }
+	}
+	x
+    }
+
+    // Square root modulo a prime by Tonelli-Shanks.
+    pub fn tonelli_shanks<Mint: ModIntTrait>(a: Mint) -> Option<Mint> {
+	let p = Mint::modulus();
+	if p == 2 || a == Mint::ZERO { return Some(a); }
+	if a.pow((p - 1) / 2) != Mint::ONE { return None; }
+	let s = (p - 1).trailing_zeros();
+	let q = (p - 1) >> s;
+	let mut z = Mint::ONE + Mint::ONE;
+	while z.pow((p - 1) / 2) == Mint::ONE { z = z + Mint::ONE; }
+	let mut c = z.pow(q);
+	let mut t = a.pow(q);
+	let mut r = a.pow((q + 1) / 2);
+	let mut m = s;
+	while t != Mint::ONE {
+	    let mut i = 0;
+	    let mut tt = t;
+	    while tt != Mint::ONE {
+		tt = tt * tt;
+		i += 1;
+	    }
+	    let b = c.pow(1 << (m - i - 1));
+	    r = r * b;
+	    c = b * b;
+	    t = t * c;
+	    m = i;
+	}
+	Some(r)
+    }
+
+    // The following assume M is prime.
+    impl<const M: u64> ModInt<M> {
+	pub const IS_PRIME: bool = is_prime(M);
+	pub const PRIMITIVE_ROOT: u64 = primitive_root(M);
+
+	pub fn primitive_root() -> Self {
+	    Self::new(Self::PRIMITIVE_ROOT)
+	}
+
+	// Smallest k > 0 with self^k = 1.
+	pub fn order(&self) -> u64 {
+	    assert!(self.0 != 0, "ERROR: order of 0 mod {}", M);
+	    let mut k = M - 1;
+	    for p in prime_factors(M - 1) {
+		while k % p == 0 && self.pow(k / p) == Self::ONE { k /= p; }
+	    }
+	    k
+	}
+
+	// Either root may be returned.
+	pub fn sqrt(&self) -> Option<Self> {
+	    tonelli_shanks(*self)
+	}
+
+	// Smallest k >= 0 with base^k = self, by baby-step giant-step.
+	pub fn log(&self, base: Self) -> Option<u64> {
+	    if *self == Self::ONE { return Some(0); }
+	    if base.0 == 0 { return if self.0 == 0 { Some(1) } else { None }; }
+	    let mut m = 1;
+	    while m * m < M { m += 1; }
+	    let mut baby = HashMap::new();
+	    let mut x = Self::ONE;
+	    for j in 0..m {
+		baby.entry(x.0).or_insert(j);
+		x *= base;
+	    }
+	    let giant = x.inv();
+	    let mut y = *self;
+	    for i in 0..m {
+		if let Some(&j) = baby.get(&y.0) { return Some(i * m + j); }
+		y *= giant;
+	    }
+	    None
+	}
+    }
+
+    // a op b from a op= b.
+    macro_rules! impl_bin_ops {
+	(($($g:tt)*), $t:ty) => {
+	    impl<$($g)*> Add for $t {
         type Output = Self;
         fn add(mut self, other: Self) -> Self {
             self += other;
@@ -64,7 +300,7 @@ mod mod_int {
         }
     }
 
-    impl<const M: u64> Sub for ModInt<M> {
+	    impl<$($g)*> Sub for $t {
         type Output = Self;
         fn sub(mut self, other: Self) -> Self {
             self -= other;
@@ -72,7 +308,7 @@ mod mod_int {
         }
     }
 
-    impl<const M: u64> Mul for ModInt<M> {
+	    impl<$($g)*> Mul for $t {
         type Output = Self;
         fn mul(mut self, other: Self) -> Self {
             self *= other;
@@ -80,13 +316,18 @@ mod mod_int {
         }
     }
 
-    impl<const M: u64> Div for ModInt<M> {
+	    // a / b panics if b is not invertible, see inv.
+	    impl<$($g)*> Div for $t {
         type Output = Self;
         fn div(mut self, other: Self) -> Self {
             self /= other;
             self
         }
     }
+	};
+    }
+
+    impl_bin_ops!((const M: u64), ModInt<M>);
 
     impl<const M: u64> AddAssign for ModInt<M> {
         fn add_assign(&mut self, other: Self) {
@@ -123,6 +364,12 @@ mod mod_int {
 	}
     }
 
+    impl<const M: u64> std::fmt::Display for ModInt<M> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+	    write!(f, "{}", self.0)
+	}
+    }
+
     impl<const M: u64> From<i64> for ModInt<M> {
         fn from(mut a: i64) -> Self {
             let m = M as i64;
@@ -138,38 +385,671 @@ mod mod_int {
         }
     }
 
+    impl<const M: u64> From<u64> for ModInt<M> {
+	fn from(a: u64) -> Self {
+	    Self((a % M) as u32)
+	}
+    }
+
+    // Odd modulus M < 2^64, kept in Montgomery form x * 2^64 mod M.
+    #[derive(Clone, Copy, Default, PartialEq)]
+    pub struct ModInt64<const M: u64>(u64);
+
+    pub type ModIntMersenne61 = ModInt64::<{ (1 << 61) - 1 }>;
+
+    impl<const M: u64> ModInt64<M> {
+	pub const ZERO: Self = Self(0);
+	pub const ONE: Self = Self(Self::R);
+	pub const MOD: u64 = M;
+	// 2^64 mod M
+	const R: u64 = ((u64::MAX % M) + 1) % M;
+	// 2^128 mod M
+	const R2: u64 = ((u128::MAX % M as u128 + 1) % M as u128) as u64;
+	// M^-1 mod 2^64
+	const M_INV: u64 = {
+	    assert!(M & 1 == 1, "ERROR: ModInt64 modulus must be odd");
+	    let mut x = M;
+	    let mut i = 0;
+	    while i < 5 {
+		x = x.wrapping_mul(2u64.wrapping_sub(M.wrapping_mul(x)));
+		i += 1;
+	    }
+	    x
+	};
+
+	// t * 2^-64 mod M, for t < M * 2^64.
+	fn reduce(t: u128) -> u64 {
+	    let m = (t as u64).wrapping_mul(Self::M_INV);
+	    let (hi, mh) = ((t >> 64) as u64, ((m as u128 * M as u128) >> 64) as u64);
+	    if hi >= mh { hi - mh } else { hi.wrapping_sub(mh).wrapping_add(M) }
+	}
+
+	pub fn new(a: u64) -> Self {
+	    Self(Self::reduce((a % M) as u128 * Self::R2 as u128))
+	}
+
+	pub fn getu(&self) -> u64 {
+	    Self::reduce(self.0 as u128)
+	}
+
+	pub fn pow(&self, mut y: u64) -> Self {
+	    let mut r = Self::ONE;
+	    let mut x = *self;
+	    while y > 0 {
+		if y & 1 == 1 { r *= x; }
+		x *= x;
+		y /= 2;
+	    }
+	    r
+	}
+
+	pub fn checked_inv(&self) -> Option<Self> {
+	    inv_gcd(self.getu() as i128, M as i128).map(|x| Self::new(x as u64))
+	}
+    }
+
+    impl<const M: u64> ModIntTrait for ModInt64<M> {
+	const ZERO: Self = ModInt64::<M>::ZERO;
+	const ONE: Self = ModInt64::<M>::ONE;
+
+	fn modulus() -> u64 {
+	    M
+	}
+
+	fn inv(&self) -> Self {
+	    self.checked_inv().expect("ERROR: ModInt64 not invertible")
+	}
+    }
+
+    impl_bin_ops!((const M: u64), ModInt64<M>);
+
+    impl<const M: u64> AddAssign for ModInt64<M> {
+	fn add_assign(&mut self, other: Self) {
+	    let (s, o) = self.0.overflowing_add(other.0);
+	    self.0 = if o || s >= M { s.wrapping_sub(M) } else { s };
+	}
+    }
+
+    impl<const M: u64> SubAssign for ModInt64<M> {
+	fn sub_assign(&mut self, other: Self) {
+	    let (s, o) = self.0.overflowing_sub(other.0);
+	    self.0 = if o { s.wrapping_add(M) } else { s };
+	}
+    }
+
+    impl<const M: u64> MulAssign for ModInt64<M> {
+	fn mul_assign(&mut self, other: Self) {
+	    self.0 = Self::reduce(self.0 as u128 * other.0 as u128);
+	}
+    }
+
+    impl<const M: u64> DivAssign for ModInt64<M> {
+	fn div_assign(&mut self, other: Self) {
+	    *self *= other.inv();
+	}
+    }
+
+    impl<const M: u64> Neg for ModInt64<M> {
+	type Output = Self;
+	fn neg(self) -> Self {
+	    if self.0 == 0 { Self::ZERO } else { Self(M - self.0) }
+	}
+    }
+
+    impl<const M: u64> std::fmt::Display for ModInt64<M> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+	    write!(f, "{}", self.getu())
+	}
+    }
+
+    impl<const M: u64> std::fmt::Debug for ModInt64<M> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+	    write!(f, "ModInt64({})", self.getu())
+	}
+    }
+
+    impl<const M: u64> From<i64> for ModInt64<M> {
+	fn from(a: i64) -> Self {
+	    Self::new((a as i128).rem_euclid(M as i128) as u64)
+	}
+    }
+
+    impl<const M: u64> From<usize> for ModInt64<M> {
+	fn from(a: usize) -> Self {
+	    Self::new(a as u64)
+	}
+    }
+
+    impl<const M: u64> From<u64> for ModInt64<M> {
+	fn from(a: u64) -> Self {
+	    Self::new(a)
+	}
+    }
+
+    // Modulus given at runtime, shared by all DynModInt of the thread:
+    //   DynModInt::set_modulus(m);
+    // 1 <= m < 2^31.  Products are reduced with Barrett reduction.
+    #[derive(Clone, Copy, Default, Debug, PartialEq)]
+    pub struct DynModInt(u32);
+
+    #[derive(Clone, Copy)]
+    struct Barrett {
+	m: u32,
+	im: u64, // ceil(2^64 / m)
+    }
+
+    impl Barrett {
+	const fn new(m: u32) -> Self {
+	    Self { m, im: (u64::MAX / m as u64).wrapping_add(1) }
+	}
+
+	fn mul(&self, a: u32, b: u32) -> u32 {
+	    let z = a as u64 * b as u64;
+	    let x = ((z as u128 * self.im as u128) >> 64) as u64;
+	    let y = x.wrapping_mul(self.m as u64);
+	    (z.wrapping_sub(y) as u32).wrapping_add(if z < y { self.m } else { 0 })
+	}
+    }
+
+    thread_local! {
+	static BARRETT: std::cell::Cell<Barrett> = const { std::cell::Cell::new(Barrett::new(998244353)) };
+    }
+
+    impl ModIntTrait for DynModInt {
+	const ZERO: Self = DynModInt::ZERO;
+	const ONE: Self = DynModInt::ONE;
+
+	fn modulus() -> u64 {
+	    DynModInt::modulus() as u64
+	}
+
+	fn inv(&self) -> Self {
+	    self.checked_inv().expect("ERROR: DynModInt not invertible")
+	}
+    }
+
+    impl DynModInt {
+	pub const ZERO: Self = Self(0);
+	pub const ONE: Self = Self(1);
+
+	pub fn set_modulus(m: u32) {
+	    assert!((1..1 << 31).contains(&m), "ERROR: DynModInt modulus out of range {}", m);
+	    BARRETT.with(|b| b.set(Barrett::new(m)));
+	}
+
+	pub fn modulus() -> u32 {
+	    BARRETT.with(|b| b.get().m)
+	}
+
+	pub fn new(a: u64) -> Self {
+	    Self((a % Self::modulus() as u64) as u32)
+	}
+
+	pub fn raw(a: u32) -> Self {
+	    Self(a)
+	}
+
+	pub fn getu(&self) -> u32 {
+	    self.0
+	}
+
+	pub fn geti(&self) -> i32 {
+	    self.0 as i32
+	}
+
+	pub fn pow(&self, mut y: u64) -> Self {
+	    let mut r = Self::new(1);
+	    let mut x = *self;
+	    while y > 0 {
+		if y & 1 == 1 { r *= x; }
+		x *= x;
+		y /= 2;
+	    }
+	    r
+	}
+
+	// Extended Euclid, so the modulus need not be prime.
+	pub fn checked_inv(&self) -> Option<Self> {
+	    inv_gcd(self.0 as i128, Self::modulus() as i128).map(|x| Self(x as u32))
+	}
+    }
+
+    impl_bin_ops!((), DynModInt);
+
+    impl AddAssign for DynModInt {
+	fn add_assign(&mut self, other: Self) {
+	    let m = Self::modulus();
+	    self.0 += other.0;
+	    if self.0 >= m { self.0 -= m; }
+	}
+    }
+
+    impl SubAssign for DynModInt {
+	fn sub_assign(&mut self, other: Self) {
+	    let m = Self::modulus();
+	    self.0 += m - other.0;
+	    if self.0 >= m { self.0 -= m; }
+	}
+    }
+
+    impl MulAssign for DynModInt {
+	fn mul_assign(&mut self, other: Self) {
+	    self.0 = BARRETT.with(|b| b.get().mul(self.0, other.0));
+	}
+    }
+
+    impl DivAssign for DynModInt {
+	fn div_assign(&mut self, other: Self) {
+	    *self *= other.inv();
+	}
+    }
+
+    impl Neg for DynModInt {
+	type Output = Self;
+	fn neg(self) -> Self {
+	    if self.0 == 0 { Self::ZERO } else { Self(Self::modulus() - self.0) }
+	}
+    }
+
+    impl std::fmt::Display for DynModInt {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+	    write!(f, "{}", self.0)
+	}
+    }
+
+    impl From<i64> for DynModInt {
+	fn from(a: i64) -> Self {
+	    Self(a.rem_euclid(Self::modulus() as i64) as u32)
+	}
+    }
+
+    impl From<usize> for DynModInt {
+	fn from(a: usize) -> Self {
+	    Self::new(a as u64)
+	}
+    }
+
+    impl From<u64> for DynModInt {
+	fn from(a: u64) -> Self {
+	    Self::new(a)
+	}
+    }
+
     pub struct Fact<Mint> {
-	fact: Vec<Mint>,
-	fact_inv: Vec<Mint>,
+	// (fact, fact_inv), grown on demand.
+	table: RefCell<(Vec<Mint>, Vec<Mint>)>,
     }
 
     // let fact = mod_int::Fact::<Mint>::new(n + 1);
     impl<Mint: ModIntTrait> Fact<Mint> {
 	pub fn new(size: usize) -> Self {
-	    let mut fact = vec![Mint::ZERO; size];
-	    let mut fact_inv = vec![Mint::ZERO; size];
-	    fact[0] = Mint::ONE;
-	    for i in 1..size {
-		fact[i] = fact[i-1] * Mint::from(i as i64);
+	    let f = Self { table: RefCell::new((vec![Mint::ONE], vec![Mint::ONE])) };
+	    if size > 0 { f.reserve(size - 1); }
+	    f
+	}
+
+	// Makes the tables cover 0..=n; n must be less than the modulus.
+	fn reserve(&self, n: usize) {
+	    let mut t = self.table.borrow_mut();
+	    let (fact, fact_inv) = &mut *t;
+	    let len = fact.len();
+	    if n < len { return; }
+	    let m = Mint::modulus();
+	    assert!((n as u64) < m, "ERROR: Fact index {} not less than modulus {}", n, m);
+	    let size = (n + 1).max(len * 2).min(m as usize);
+	    for i in len..size {
+		fact.push(fact[i-1] * Mint::from(i as i64));
 	    }
+	    fact_inv.resize(size, Mint::ZERO);
 	    fact_inv[size-1] = fact[size-1].inv();
-	    for i in (1..size).rev() {
+	    for i in (len..size).rev() {
 		fact_inv[i-1] = fact_inv[i] * Mint::from(i as i64);
 	    }
-	    Self {fact, fact_inv}
 	}
 
+	pub fn fact(&self, n: usize) -> Mint {
+	    self.reserve(n);
+	    self.table.borrow().0[n]
+	}
+
+	pub fn fact_inv(&self, n: usize) -> Mint {
+	    self.reserve(n);
+	    self.table.borrow().1[n]
+	}
+
+	// 1 / n for 1 <= n
+	pub fn inv(&self, n: usize) -> Mint {
+	    assert!(n > 0, "ERROR: Fact::inv(0)");
+	    self.fact_inv(n) * self.fact(n-1)
+	}
+
+	// 0 if k > n.
 	pub fn nck(&self, n: usize, k: usize) -> Mint {
-	    self.fact[n] * self.fact_inv[k] * self.fact_inv[n-k]
+	    if k > n { return Mint::ZERO; }
+	    self.fact(n) * self.fact_inv(k) * self.fact_inv(n-k)
 	}
 
-	pub fn fact(&self, n: usize) -> Mint {
-	    self.fact[n]
+	pub fn npk(&self, n: usize, k: usize) -> Mint {
+	    if k > n { return Mint::ZERO; }
+	    self.fact(n) * self.fact_inv(n-k)
 	}
 
-	pub fn fact_inv(&self, n: usize) -> Mint {
-	    self.fact_inv[n]
+	// Multisets of size k from n kinds.
+	pub fn nhk(&self, n: usize, k: usize) -> Mint {
+	    if n == 0 { return if k == 0 { Mint::ONE } else { Mint::ZERO }; }
+	    self.nck(n + k - 1, k)
+	}
+
+	// (k_1 + ... + k_m)! / (k_1! ... k_m!)
+	pub fn multinomial(&self, ks: &[usize]) -> Mint {
+	    let mut r = self.fact(ks.iter().sum());
+	    for &k in ks { r = r * self.fact_inv(k); }
+	    r
+	}
+
+	pub fn catalan(&self, n: usize) -> Mint {
+	    self.nck(2 * n, n) - self.nck(2 * n, n + 1)
+	}
+
+	// Lucas' theorem, for a prime modulus p; the tables hold up to p - 1.
+	pub fn lucas(&self, mut n: u64, mut k: u64) -> Mint {
+	    let p = Mint::modulus();
+	    let mut r = Mint::ONE;
+	    while k > 0 {
+		r = r * self.nck((n % p) as usize, (k % p) as usize);
+		n /= p;
+		k /= p;
+	    }
+	    r
+	}
+
+	// Partitions of n labeled items into k non-empty sets, O(k log n).
+	pub fn stirling2(&self, n: usize, k: usize) -> Mint {
+	    let mut r = Mint::ZERO;
+	    for i in 0..=k {
+		let t = self.fact_inv(i) * self.fact_inv(k - i) * Mint::from(i as i64).pow(n as u64);
+		r = if (k - i) % 2 == 0 { r + t } else { r - t };
+	    }
+	    r
+	}
+
+	// Partitions of n labeled items, O(n log n).
+	pub fn bell(&self, n: usize) -> Mint {
+	    // sum_i i^n / i! * sum_{j <= n - i} (-1)^j / j!
+	    let mut alt = vec![Mint::ZERO; n + 1];
+	    for j in 0..=n {
+		let t = if j % 2 == 0 { self.fact_inv(j) } else { Mint::ZERO - self.fact_inv(j) };
+		alt[j] = if j == 0 { t } else { alt[j-1] + t };
+	    }
+	    let mut r = if n == 0 { Mint::ONE } else { Mint::ZERO };
+	    for i in 1..=n {
+		r = r + Mint::from(i as i64).pow(n as u64) * self.fact_inv(i) * alt[n - i];
+	    }
+	    r
+	}
+    }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+	use crate::stress::XorShift;
+	type Mint = ModInt998244353;
+
+	#[test]
+	fn arithmetic() {
+	    let m = 998244353i64;
+	    for a in [0i64, 1, 2, 12345, m - 1, -1, -m - 3] {
+		for b in [0i64, 1, 7, m - 2, 998244] {
+		    let (x, y) = (Mint::from(a), Mint::from(b));
+		    let (a, b) = (a.rem_euclid(m), b.rem_euclid(m));
+		    assert_eq!((x + y).getu() as i64, (a + b) % m);
+		    assert_eq!((x - y).getu() as i64, (a - b).rem_euclid(m));
+		    assert_eq!((x * y).getu() as i64, a * b % m);
+		    assert_eq!((-x).getu() as i64, (m - a) % m);
+		    if b != 0 {
+			assert_eq!(x / y * y, x);
+		    }
+		}
+	    }
+	    let x = Mint::new(3);
+	    let mut p = Mint::ONE;
+	    for e in 0..20 {
+		assert_eq!(x.pow(e), p);
+		p *= x;
+	    }
+	    assert_eq!(format!("{}", Mint::from(-1i64)), "998244352");
+	}
+
+	#[test]
+	fn fact() {
+	    let n = 30;
+	    let f = Fact::<Mint>::new(n + 1);
+	    let mut c = vec![vec![Mint::ZERO; n + 1]; n + 1];
+	    for i in 0..=n {
+		c[i][0] = Mint::ONE;
+		for k in 1..=i {
+		    c[i][k] = c[i - 1][k - 1] + if k < i { c[i - 1][k] } else { Mint::ZERO };
+		}
+		for k in 0..=i {
+		    assert_eq!(f.nck(i, k), c[i][k]);
+		}
+		assert_eq!(f.fact(i) * f.fact_inv(i), Mint::ONE);
+		assert_eq!(f.nck(i, i + 1), Mint::ZERO);
+		if i > 0 { assert_eq!(f.inv(i) * Mint::new(i as u64), Mint::ONE); }
+	    }
+	    assert_eq!(f.npk(5, 2), Mint::new(20));
+	    assert_eq!(f.npk(2, 5), Mint::ZERO);
+	    assert_eq!(f.nhk(3, 2), Mint::new(6));
+	    assert_eq!(f.nhk(0, 0), Mint::ONE);
+	    assert_eq!(f.nhk(0, 1), Mint::ZERO);
+	    assert_eq!(f.multinomial(&[2, 1, 1]), Mint::new(12));
+	    let catalan = [1, 1, 2, 5, 14, 42, 132, 429];
+	    for i in 0..catalan.len() {
+		assert_eq!(f.catalan(i), Mint::new(catalan[i]));
+	    }
+	}
+
+	#[test]
+	fn fact_grows() {
+	    let f = Fact::<Mint>::new(0);
+	    assert_eq!(f.fact(0), Mint::ONE);
+	    assert_eq!(f.nck(1000, 3), Mint::new(1000 * 999 * 998 / 6));
+	    assert_eq!(f.fact(1000) * f.fact_inv(1000), Mint::ONE);
+	    assert_eq!(f.fact(1001), f.fact(1000) * Mint::new(1001));
+	}
+
+	#[test]
+	fn stirling_bell() {
+	    let n = 20;
+	    let f = Fact::<Mint>::new(0);
+	    let mut s = vec![vec![Mint::ZERO; n + 1]; n + 1];
+	    s[0][0] = Mint::ONE;
+	    for i in 1..=n {
+		for k in 1..=i {
+		    s[i][k] = s[i - 1][k - 1] + Mint::new(k as u64) * s[i - 1][k];
+		}
+	    }
+	    for i in 0..=n {
+		let mut bell = Mint::ZERO;
+		for k in 0..=n {
+		    assert_eq!(f.stirling2(i, k), s[i][k]);
+		    bell += s[i][k];
+		}
+		assert_eq!(f.bell(i), bell);
+	    }
+	}
+
+	#[test]
+	fn lucas() {
+	    type M13 = ModInt<13>;
+	    let f = Fact::<M13>::new(0);
+	    let n = 200;
+	    let mut c = vec![0u64; n + 1];
+	    c[0] = 1;
+	    for i in 0..=n {
+		for k in 0..=n {
+		    assert_eq!(f.lucas(i as u64, k as u64), M13::new(c[k]));
+		}
+		for k in (1..=n).rev() { c[k] = (c[k] + c[k - 1]) % 13; }
+	    }
+	    assert_eq!(f.lucas(1 << 60, 1 << 59), M13::new(0));
+	    assert_eq!(f.lucas(13u64.pow(15) + 5, 2), M13::new(10));
+	}
+
+	#[test]
+	fn dyn_mod_int() {
+	    type Fixed = ModInt1000000007;
+	    DynModInt::set_modulus(1000000007);
+	    let mut rng = XorShift::new(1);
+	    for _ in 0..1000 {
+		let x = rng.next_u64();
+		let (a, b) = (x >> 3, x % 1000);
+		let (p, q) = (DynModInt::new(a), DynModInt::new(b));
+		let (s, t) = (Fixed::new(a), Fixed::new(b));
+		assert_eq!((p * q).getu(), (s * t).getu());
+		assert_eq!((p + q).getu(), (s + t).getu());
+		assert_eq!((p - q).getu(), (s - t).getu());
+		assert_eq!((-p).getu(), (-s).getu());
+		assert_eq!(p.pow(b).getu(), s.pow(b).getu());
+		if b != 0 { assert_eq!((p / q).getu(), (s / t).getu()); }
+	    }
+	    for m in [1u32, 2, 3, 1000, 65537, (1 << 31) - 1] {
+		DynModInt::set_modulus(m);
+		for _ in 0..1000 {
+		    let x = rng.next_u64();
+		    let (a, b) = (x % m as u64, (x >> 32) % m as u64);
+		    assert_eq!((DynModInt::new(a) * DynModInt::new(b)).getu() as u64, a * b % m as u64);
+		}
+	    }
+	    DynModInt::set_modulus(1000000);
+	    assert_eq!(DynModInt::new(10).checked_inv(), None);
+	    assert_eq!(DynModInt::new(7).inv() * DynModInt::new(7), DynModInt::ONE);
+	    assert_eq!(DynModInt::from(-3i64).getu(), 999997);
+	    DynModInt::set_modulus(13);
+	    let f = Fact::<DynModInt>::new(13);
+	    assert_eq!(f.nck(12, 5).getu(), 792 % 13);
+	    let mut a = crate::matrix::Matrix::<DynModInt, 2>::default();
+	    (a[0][0], a[0][1], a[1][0]) = (DynModInt::ONE, DynModInt::ONE, DynModInt::ONE);
+	    let b = &a * &a;
+	    assert_eq!((&b * &b)[0][1], DynModInt::new(3)); // fib(4) mod 13
+	}
+
+	fn check_mod_int64<const M: u64>() {
+	    let m = M as u128;
+	    let mut rng = XorShift::new(1);
+	    for _ in 0..1000 {
+		let x = rng.next_u64();
+		let (a, b) = (x, x.rotate_left(29) ^ 12345);
+		let (p, q) = (ModInt64::<M>::new(a), ModInt64::<M>::new(b));
+		let (a, b) = (a as u128 % m, b as u128 % m);
+		assert_eq!(p.getu() as u128, a);
+		assert_eq!((p * q).getu() as u128, a * b % m);
+		assert_eq!((p + q).getu() as u128, (a + b) % m);
+		assert_eq!((p - q).getu() as u128, (a + m - b) % m);
+		assert_eq!((-p).getu() as u128, (m - a) % m);
+		if b != 0 { assert_eq!(p / q * q, p); }
+	    }
+	    assert_eq!(ModInt64::<M>::from(-1i64).getu(), M - 1);
+	    assert_eq!(ModInt64::<M>::new(3).pow(M - 1), ModInt64::ONE);
+	}
+
+	#[test]
+	fn mod_int64() {
+	    check_mod_int64::<998244353>();
+	    check_mod_int64::<{ (1 << 61) - 1 }>();
+	    check_mod_int64::<18446744073709551557>();
+	    assert_eq!(format!("{:?}", ModIntMersenne61::new(5)), "ModInt64(5)");
+	    assert_eq!(ModInt64::<15>::new(5).checked_inv(), None);
+	}
+
+	#[test]
+	fn number_theory() {
+	    assert_eq!(ModInt998244353::PRIMITIVE_ROOT, 3);
+	    assert_eq!(ModInt1000000007::PRIMITIVE_ROOT, 5);
+	    assert_eq!(ModInt::<2>::PRIMITIVE_ROOT, 1);
+	    assert_eq!(ModInt998244353::primitive_root().order(), 998244352);
+	    fn check<const M: u64>() {
+		for a in 0..M {
+		    let x = ModInt::<M>::new(a);
+		    let squares = (0..M).any(|b| b * b % M == a);
+		    match x.sqrt() {
+			Some(r) => assert_eq!(r * r, x),
+			None => assert!(!squares),
+		    }
+		    if a == 0 { continue; }
+		    let order = (1..M).find(|&k| x.pow(k) == ModInt::ONE).unwrap();
+		    assert_eq!(x.order(), order);
+		    for b in 0..M {
+			let base = ModInt::<M>::new(b);
+			let log = (0..M).find(|&k| base.pow(k) == x);
+			assert_eq!(x.log(base), log);
+		    }
+		}
+		let g = ModInt::<M>::primitive_root();
+		assert_eq!(g.order(), M - 1);
+	    }
+	    check::<2>();
+	    check::<13>();
+	    check::<17>();
+	    check::<97>();
+	    type P = ModInt998244353;
+	    let x = P::new(123456789);
+	    assert_eq!((x * x).sqrt().map(|r| r == x || r == -x), Some(true));
+	    assert_eq!(P::new(5).pow(987654).log(P::new(5)), Some(987654));
+	    assert_eq!(P::primitive_root().sqrt(), None);
+	}
+
+	#[test]
+	fn crt_garner() {
+	    let mut rng = XorShift::new(1);
+	    for _ in 0..2000 {
+		let mut rm = Vec::new();
+		for _ in 0..3 {
+		    let x = rng.next_u64();
+		    rm.push(((x >> 20) as i64 % 41 - 20, (x % 12 + 1) as i64));
+		}
+		let lcm = rm.iter().fold(1, |l, &(_, m)| { let mut g = l; let mut h = m; while h != 0 { (g, h) = (h, g % h); } l / g * m });
+		let brute = (0..lcm).find(|&v| rm.iter().all(|&(r, m)| (v - r).rem_euclid(m) == 0));
+		assert_eq!(crt(&rm), brute.map(|v| (v, lcm)));
+	    }
+	    assert_eq!(crt(&[]), Some((0, 1)));
+	    assert_eq!(crt(&[(5, 1_000_000_007), (7, 998_244_353)]).map(|(r, m)| (r % 1_000_000_007, r % 998_244_353, m)),
+		       Some((5, 7, 1_000_000_007 * 998_244_353)));
+
+	    let ps = [998244353u64, 167772161, 469762049];
+	    for _ in 0..1000 {
+		let x = rng.next_u64();
+		let v = x as u128 * (x >> 40) as u128 % (ps[0] as u128 * ps[1] as u128 * ps[2] as u128);
+		let rm: Vec<_> = ps.iter().map(|&p| ((v % p as u128) as u64, p)).collect();
+		assert_eq!(garner_u128(&rm), v);
+		assert_eq!(garner(&rm, 1000000007), (v % 1000000007) as u64);
+	    }
+	}
+
+	#[test]
+	fn composite_modulus() {
+	    type M6 = ModInt<1000000>;
+	    for a in 0..2000u64 {
+		let x = M6::new(a);
+		match x.checked_inv() {
+		    Some(y) => assert_eq!(x * y, M6::ONE),
+		    None => assert!(a % 2 == 0 || a % 5 == 0),
+		}
+	    }
+	    assert_eq!(M6::new(3) / M6::new(7) * M6::new(7), M6::new(3));
+	    assert_eq!(M6::new(10).checked_inv(), None);
+	}
+
+	#[test]
+	#[should_panic(expected = "does not fit in i64")]
+	fn crt_lcm_overflow() {
+	    let _ = crt(&[(1, 1_000_000_007), (2, 998_244_353), (3, 1_000_000_009)]);
+	}
+
+	#[test]
+	#[should_panic(expected = "not invertible")]
+	fn division_by_non_invertible() {
+	    let _ = ModInt::<1000000>::new(3) / ModInt::<1000000>::new(4);
 	}
     }
 }
-type Mint = mod_int::ModInt998244353;
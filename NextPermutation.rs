@@ -1,57 +1,70 @@
-trait NextPermutation {
-    fn next_permutation(&mut self) -> bool;
-    fn prev_permutation(&mut self) -> bool;
-}
+pub mod next_permutation {
+    pub trait NextPermutation {
+        fn next_permutation(&mut self) -> bool;
+        fn prev_permutation(&mut self) -> bool;
+    }
 
-impl<T: PartialOrd> NextPermutation for [T] {
-    fn next_permutation(&mut self) -> bool {
-        let n = self.len();
-        if n <= 1 { return false; }
+    impl<T: PartialOrd> NextPermutation for [T] {
+        fn next_permutation(&mut self) -> bool {
+            let n = self.len();
+            if n <= 1 { return false; }
 
-        for i in (0..n-1).rev() {
-            if self[i] < self[i+1] {
-                for j in (0..n).rev() {
-                    if self[i] < self[j] {
-                        self.swap(i, j);
-                        self[i+1..].reverse();
-                        return true;
+            for i in (0..n-1).rev() {
+                if self[i] < self[i+1] {
+                    for j in (0..n).rev() {
+                        if self[i] < self[j] {
+                            self.swap(i, j);
+                            self[i+1..].reverse();
+                            return true;
+                        }
                     }
                 }
             }
+            self.reverse();
+            false
         }
-        self.reverse();
-        false
-    }
 
-    fn prev_permutation(&mut self) -> bool {
-        let n = self.len();
-        if n <= 1 { return false; }
+        fn prev_permutation(&mut self) -> bool {
+            let n = self.len();
+            if n <= 1 { return false; }
 
-        for i in (0..n-1).rev() {
-            if self[i] > self[i+1] {
-                for j in (0..n).rev() {
-                    if self[i] > self[j] {
-                        self.swap(i, j);
-                        self[i+1..].reverse();
-                        return true;
+            for i in (0..n-1).rev() {
+                if self[i] > self[i+1] {
+                    for j in (0..n).rev() {
+                        if self[i] > self[j] {
+                            self.swap(i, j);
+                            self[i+1..].reverse();
+                            return true;
+                        }
                     }
                 }
             }
+            self.reverse();
+            false
         }
-        self.reverse();
-        false
     }
-}
-
-
-fn main() {
-    let mut v = vec![1, 2, 2, 3];
-
-    while {
-        println!("{:?}", v);
 
-        v.next_permutation()
-    } {}
+    #[cfg(test)]
+    mod tests {
+        use super::*;
 
-    println!("> {:?}", v);
-}
+        #[test]
+        fn multiset() {
+            let mut v = vec![1, 2, 2, 3];
+            let mut seen = vec![v.clone()];
+            while v.next_permutation() {
+                assert!(seen.last().unwrap() < &v);
+                seen.push(v.clone());
+            }
+            assert_eq!(seen.len(), 12);
+            assert_eq!(v, vec![1, 2, 2, 3]);
+            let mut v = vec![3, 2, 2, 1];
+            let mut count = 1;
+            while v.prev_permutation() {
+                count += 1;
+            }
+            assert_eq!(count, 12);
+            assert_eq!(v, vec![3, 2, 2, 1]);
+        }
+    }
+} // mod next_permutation;
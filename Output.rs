@@ -1,3 +1,113 @@
-fn main() {
-    println!("{}", val.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(" "));
-}
+#[macro_use]
+pub mod output {
+    use std::fmt::Display;
+    use std::io::{BufWriter, StdoutLock, Write};
+    use crate::grid::Grid;
+
+    macro_rules! out {
+	($out:expr, $($arg:tt)*) => {{
+	    #[allow(unused_imports)]
+	    use std::io::Write as _;
+	    $out.write_fmt(format_args!($($arg)*)).expect("ERROR: failed to write output")
+	}};
+    }
+
+    macro_rules! outln {
+	($out:expr) => {
+	    out!($out, "\n")
+	};
+	($out:expr, $($arg:tt)*) => {
+	    out!($out, "{}\n", format_args!($($arg)*))
+	};
+    }
+
+    // let mut out = output::Output::stdout();
+    // outln!(out, "{} {}", a, b);
+    // Everything is buffered and flushed when `out` is dropped.
+    pub struct Output<W: Write> {
+	w: BufWriter<W>,
+    }
+
+    impl Output<StdoutLock<'static>> {
+	pub fn stdout() -> Self {
+	    Self::new(std::io::stdout().lock())
+	}
+    }
+
+    #[allow(dead_code)]
+    impl<W: Write> Output<W> {
+	pub fn new(w: W) -> Self {
+	    Self { w: BufWriter::with_capacity(1 << 16, w) }
+	}
+
+	pub fn join<T: Display>(&mut self, a: &[T], sep: &str) {
+	    for (i, x) in a.iter().enumerate() {
+		if i > 0 { out!(self, "{}", sep); }
+		out!(self, "{}", x);
+	    }
+	}
+
+	// One line, space separated.
+	pub fn spaced<T: Display>(&mut self, a: &[T]) {
+	    self.join(a, " ");
+	    outln!(self);
+	}
+
+	// One value per line.
+	pub fn lines<T: Display>(&mut self, a: &[T]) {
+	    for x in a {
+		outln!(self, "{}", x);
+	    }
+	}
+
+	// One row per line; sep is "" for char grids.
+	pub fn grid<T: Display>(&mut self, g: &Grid<T>, sep: &str) {
+	    for i in 0..g.height() {
+		self.join(&g[i], sep);
+		outln!(self);
+	    }
+	}
+
+	pub fn yes_no(&mut self, b: bool) {
+	    outln!(self, "{}", if b { "Yes" } else { "No" });
+	}
+    }
+
+    impl<W: Write> Write for Output<W> {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+	    self.w.write(buf)
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+	    self.w.flush()
+	}
+    }
+
+    impl<W: Write> Drop for Output<W> {
+	fn drop(&mut self) {
+	    self.w.flush().ok();
+	}
+    }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+
+	#[test]
+	fn helpers() {
+	    let mut buf = Vec::new();
+	    {
+		let mut out = Output::new(&mut buf);
+		out.spaced(&[1, 2, 3]);
+		out.lines(&["a", "b"]);
+		let mut g = Grid::new(2, 2, '.');
+		g[0][1] = '#';
+		out.grid(&g, "");
+		out.yes_no(false);
+		outln!(out, "{}-{}", 4, 5);
+		out!(out, "end");
+	    }
+	    assert_eq!(String::from_utf8(buf).unwrap(), "1 2 3\na\nb\n.#\n..\nNo\n4-5\nend");
+	}
+    }
+} // mod output;
@@ -1,12 +1,50 @@
-mod polynomial {
-    use crate::mod_int::{ModInt998244353, ModIntTrait};
-    type Mint = ModInt998244353;
+pub mod polynomial {
+    use crate::mod_int::{garner, garner_u128, tonelli_shanks, DynModInt, Fact, ModInt, ModInt64, ModIntTrait};
+    use std::any::{Any, TypeId};
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::fmt::Debug;
     use std::ops::*;
+    use std::rc::Rc;
+
+    // Coefficients of a Polynomial; max_ntt_len() is 1 when only the naive O(nm) product works.
+    pub trait NttFriendly: ModIntTrait + AddAssign + SubAssign + MulAssign + Div<Output=Self> + Neg<Output=Self> + Debug + 'static {
+	// Largest power of two dividing the multiplicative group order.
+	fn max_ntt_len() -> usize;
+	fn primitive_root() -> Self;
+    }
+
+    impl<const M: u64> NttFriendly for ModInt<M> {
+	fn max_ntt_len() -> usize {
+	    if ModInt::<M>::IS_PRIME { 1 << (M - 1).trailing_zeros() } else { 1 }
+	}
+	fn primitive_root() -> Self {
+	    ModInt::<M>::primitive_root()
+	}
+    }
+
+    impl<const M: u64> NttFriendly for ModInt64<M> {
+	fn max_ntt_len() -> usize {
+	    1
+	}
+	fn primitive_root() -> Self {
+	    panic!("ERROR: no NTT for ModInt64")
+	}
+    }
+
+    impl NttFriendly for DynModInt {
+	fn max_ntt_len() -> usize {
+	    1
+	}
+	fn primitive_root() -> Self {
+	    panic!("ERROR: no NTT for DynModInt")
+	}
+    }
 
     #[derive(Debug)]
-    pub struct Polynomial(Vec<Mint>);
+    pub struct Polynomial<Mint>(Vec<Mint>);
 
-    impl Polynomial {
+    impl<Mint: NttFriendly> Polynomial<Mint> {
 	pub fn new() -> Self {
 	    Self(Vec::new())
 	}
@@ -53,7 +91,7 @@ mod polynomial {
 	    }
 	}
 
-	pub fn inv(&self, k: usize) -> Polynomial {
+	pub fn inv(&self, k: usize) -> Polynomial<Mint> {
 	    if self.0.is_empty() || self.0[0] == Mint::ZERO {
 		return Polynomial::new();
 	    }
@@ -69,7 +107,7 @@ mod polynomial {
 	    z.mod_xk(k)
 	}
 
-	fn mul_xk(&self, k: usize) -> Polynomial {
+	fn mul_xk(&self, k: usize) -> Polynomial<Mint> {
 	    if self.0.is_empty() {
 		self.clone()
 	    } else {
@@ -79,7 +117,7 @@ mod polynomial {
 	    }
 	}
 
-	pub fn mod_xk(&self, k: usize) -> Polynomial {
+	pub fn mod_xk(&self, k: usize) -> Polynomial<Mint> {
 	    if self.0.len() <= k {
 		self.clone()
 	    } else {
@@ -87,7 +125,7 @@ mod polynomial {
 	    }
 	}
 
-	fn substr(&self, l: usize, mut r: usize) -> Polynomial {
+	fn substr(&self, l: usize, mut r: usize) -> Polynomial<Mint> {
 	    r = r.min(self.0.len());
 	    if r <= l {
 		Polynomial::new()
@@ -96,7 +134,7 @@ mod polynomial {
 	    }
 	}
 
-	pub fn log(&self, k: usize) -> Polynomial {
+	pub fn log(&self, k: usize) -> Polynomial<Mint> {
 	    assert!(self.get(0) == Mint::ONE);
 	    if k <= 1 {
 		Polynomial::new()
@@ -105,19 +143,19 @@ mod polynomial {
 	    }
 	}
 
-	pub fn deriv(&self) -> Polynomial {
+	pub fn deriv(&self) -> Polynomial<Mint> {
 	    if self.0.len() <= 1 { return Polynomial::new(); }
-	    Polynomial((1..self.0.len()).map(|i| self.0[i] * Mint::raw(i as u32)).collect())
+	    Polynomial((1..self.0.len()).map(|i| self.0[i] * Mint::from(i as i64)).collect())
 	}
 
-	pub fn integr(&self) -> Polynomial {
+	pub fn integr(&self) -> Polynomial<Mint> {
 	    if self.0.is_empty() { return Polynomial::new(); }
 	    let mut v = vec![Mint::ZERO; self.0.len() + 1];
-	    for i in 0..self.0.len() { v[i + 1] = self.0[i] / Mint::raw((i + 1) as u32); }
+	    for i in 0..self.0.len() { v[i + 1] = self.0[i] / Mint::from((i + 1) as i64); }
 	    Polynomial(v)
 	}
 
-	pub fn exp(&self, k: usize) -> Polynomial {
+	pub fn exp(&self, k: usize) -> Polynomial<Mint> {
 	    assert!(self.get(0) == Mint::ZERO);
 	    if k == 0 {
 		Polynomial::new()
@@ -137,7 +175,7 @@ mod polynomial {
 	    }
 	}
 
-	pub fn pow(&self, y: u64, k: usize) -> Polynomial {
+	pub fn pow(&self, y: u64, k: usize) -> Polynomial<Mint> {
 	    let mut t = self.0.len();
 	    for i in 0..self.0.len() {
 		if self.0[i] != Mint::ZERO {
@@ -151,28 +189,366 @@ mod polynomial {
 	    if t != 0 && y >= (k as u64) / (t as u64) { return Polynomial::new(); }
 	    let yt = (y * (t as u64)) as usize;
 	    let w = k - yt;
-	    (((self.substr(t, self.0.len()).mod_xk(w) * alpha_inv).log(w) * Mint::new(y)).exp(w) * alpha.pow(y)).mul_xk(yt)
+	    (((self.substr(t, self.0.len()).mod_xk(w) * alpha_inv).log(w) * Mint::from(y % Mint::modulus())).exp(w) * alpha.pow(y)).mul_xk(yt)
+
+
+	}
+
+	pub fn eval(&self, x: Mint) -> Mint {
+	    self.0.iter().rev().fold(Mint::ZERO, |s, &c| s * x + c)
+	}
+
+	fn reversed(&self) -> Polynomial<Mint> {
+	    Polynomial::from(self.0.iter().rev().copied().collect())
+	}
+
+	// (q, r) with self = q * other + r, deg r < deg other.
+	pub fn div_rem(&self, other: &Polynomial<Mint>) -> (Polynomial<Mint>, Polynomial<Mint>) {
+	    let a = Polynomial::from(self.0.clone());
+	    let b = Polynomial::from(other.0.clone());
+	    assert!(!b.0.is_empty(), "ERROR: Polynomial division by zero");
+	    if a.0.len() < b.0.len() {
+		return (Polynomial::new(), a);
+	    }
+	    let k = a.0.len() - b.0.len() + 1;
+	    let mut q = (&a.reversed().mod_xk(k) * &b.reversed().inv(k)).mod_xk(k).0;
+	    q.resize(k, Mint::ZERO);
+	    q.reverse();
+	    let q = Polynomial::from(q);
+	    let r = &a - &(&b * &q);
+	    (q, r)
+	}
+
+	// Some g with g^2 = self mod x^k, or None if there is none.
+	pub fn sqrt(&self, k: usize) -> Option<Polynomial<Mint>> {
+	    let t = match self.0.iter().position(|&c| c != Mint::ZERO) {
+		Some(t) => t,
+		None => return Some(Polynomial::new()),
+	    };
+	    if t % 2 == 1 { return None; }
+	    if t / 2 >= k { return Some(Polynomial::new()); }
+	    let w = k - t / 2;
+	    let f = self.substr(t, t + w);
+	    let mut z = Polynomial::from(vec![tonelli_shanks(f.0[0])?]);
+	    let half = Mint::from(2i64).inv();
+	    let mut m = 1;
+	    while m < w {
+		m += m;
+		z = (&(&z + &(&f.mod_xk(m) * &z.inv(m)).mod_xk(m)) * half).mod_xk(m);
+	    }
+	    Some(Polynomial::from(z.mod_xk(w).0).mul_xk(t / 2))
+	}
+
+	// f(x + c)
+	pub fn taylor_shift(&self, c: Mint) -> Polynomial<Mint> {
+	    let n = self.0.len();
+	    if n == 0 { return Polynomial::new(); }
+	    let fact = Fact::<Mint>::new(n);
+	    // b_j j! = sum_i a_i i! c^(i-j) / (i-j)!
+	    let a: Vec<Mint> = (0..n).rev().map(|i| self.0[i] * fact.fact(i)).collect();
+	    let mut e = vec![Mint::ONE; n];
+	    for i in 1..n { e[i] = e[i-1] * c; }
+	    let e: Vec<Mint> = (0..n).map(|i| e[i] * fact.fact_inv(i)).collect();
+	    let b = convolution_impl(a, e);
+	    Polynomial::from((0..n).map(|j| b[n-1-j] * fact.fact_inv(j)).collect())
+	}
 
+	// f(a x + b)
+	pub fn compose_with_linear(&self, a: Mint, b: Mint) -> Polynomial<Mint> {
+	    let mut g = self.taylor_shift(b);
+	    let mut p = Mint::ONE;
+	    for c in g.0.iter_mut() {
+		*c *= p;
+		p *= a;
+	    }
+	    Polynomial::from(g.0)
+	}
+
+	// f(m), ..., f(m + cnt - 1) from ys = f(0), ..., f(n - 1), for deg f < n.
+	pub fn shift_of_sampling_points(ys: &[Mint], m: u64, cnt: usize) -> Vec<Mint> {
+	    let n = ys.len();
+	    let p = Mint::modulus();
+	    let m = m % p;
+	    if cnt == 0 { return Vec::new(); }
+	    if n == 0 { return vec![Mint::ZERO; cnt]; }
+	    if m < n as u64 {
+		// f(m..n) are known; continue from f(n).
+		let k = (n - m as usize).min(cnt);
+		let mut r = ys[m as usize..m as usize + k].to_vec();
+		r.append(&mut Self::shift_of_sampling_points(ys, n as u64, cnt - k));
+		return r;
+	    }
+	    if cnt as u64 > p - m {
+		// f is periodic mod p; wrap around to f(0).
+		let k = (p - m) as usize;
+		let mut r = Self::shift_of_sampling_points(ys, m, k);
+		r.append(&mut Self::shift_of_sampling_points(ys, 0, cnt - k));
+		return r;
+	    }
+	    // f(m + k) = prod_{j < n} (m + k - j) sum_i w_i / (m + k - i), w_i = ys_i / prod_{j != i} (i - j).
+	    let fact = Fact::<Mint>::new(n);
+	    let w: Vec<Mint> = (0..n).map(|i| {
+		let w = ys[i] * fact.fact_inv(i) * fact.fact_inv(n-1-i);
+		if (n - 1 - i) % 2 == 0 { w } else { -w }
+	    }).collect();
+	    // g_t = 1 / (s + t), s = m - n + 1 >= 1
+	    let s = Mint::from(m - n as u64 + 1);
+	    let g: Vec<Mint> = (0..n + cnt - 1).map(|t| (s + Mint::from(t as u64)).inv()).collect();
+	    let h = convolution_impl(w, g.clone());
+	    let mut prod = (0..n).fold(Mint::ONE, |p, t| p * (s + Mint::from(t as u64)));
+	    let mut r = Vec::with_capacity(cnt);
+	    for k in 0..cnt {
+		r.push(prod * h[k+n-1]);
+		prod = prod * (s + Mint::from((k + n) as u64)) * g[k];
+	    }
+	    r
+	}
 
+	pub fn multipoint_eval(&self, xs: &[Mint]) -> Vec<Mint> {
+	    if xs.is_empty() { return Vec::new(); }
+	    let m = xs.len();
+	    let d = if m > 1 { xs[1] - xs[0] } else { Mint::ZERO };
+	    if m > NAIVE_LEN && d != Mint::ZERO && (1..m).all(|i| xs[i] - xs[i-1] == d)
+		&& ((self.0.len().min(m).next_power_of_two()) as u64) < Mint::modulus() {
+		return self.eval_arithmetic(xs[0], d, m);
+	    }
+	    let tree = subproduct_tree(xs);
+	    let mut ys = vec![Mint::ZERO; xs.len()];
+	    descend(&tree, 1, self % &tree[1], 0, tree.len() / 2, xs, &mut ys);
+	    ys
 	}
+
+	// f(a), f(a + d), ..., f(a + (m - 1) d) without a subproduct tree:
+	// g(t) = f(a + d t) has deg g < sz, and is evaluated at t = 0, 1, ..., m - 1 in blocks of sz.
+	fn eval_arithmetic(&self, a: Mint, d: Mint, m: usize) -> Vec<Mint> {
+	    let mut g = self.compose_with_linear(d, a);
+	    let sz = g.0.len().min(m).next_power_of_two();
+	    // fall[j] = t (t - 1) ... (t - 2^j + 1)
+	    let mut fall = vec![Polynomial::from(vec![Mint::ZERO, Mint::ONE])];
+	    while fall.len() <= sz.trailing_zeros() as usize {
+		let f = fall.last().unwrap();
+		let h = Mint::from((f.0.len() - 1) as i64);
+		fall.push(f * &f.taylor_shift(-h));
+	    }
+	    if g.0.len() > sz { g = &g % &fall[fall.len() - 1]; }
+	    // every remainder at level j is by fall[j - 1], so its reversed inverse is shared
+	    let fall: Vec<_> = fall.into_iter().map(|f| {
+		let inv = f.reversed().inv(f.0.len() - 1);
+		(f, inv)
+	    }).collect();
+	    let mut ys = vec![Mint::ZERO; m];
+	    for (b, block) in ys.chunks_mut(sz).enumerate() {
+		let h = g.taylor_shift(Mint::from((b * sz) as i64));
+		eval_consecutive(h, &fall, fall.len() - 1, block);
+	    }
+	    ys
+	}
+
+	// The polynomial of degree < points.len() through the points; the x must be distinct.
+	pub fn interpolate(points: &[(Mint, Mint)]) -> Polynomial<Mint> {
+	    let n = points.len();
+	    if n == 0 { return Polynomial::new(); }
+	    let xs: Vec<Mint> = points.iter().map(|p| p.0).collect();
+	    let tree = subproduct_tree(&xs);
+	    // w_i = prod_{j != i} (x_i - x_j), in O(n) for an arithmetic progression x_i = x_0 + d i.
+	    let d = if n > 1 { xs[1] - xs[0] } else { Mint::ZERO };
+	    let w = if d != Mint::ZERO && (1..n).all(|i| xs[i] - xs[i-1] == d) {
+		let mut fact = vec![Mint::ONE; n];
+		for i in 1..n { fact[i] = fact[i-1] * Mint::from(i as i64); }
+		let dn = d.pow(n as u64 - 1);
+		(0..n).map(|i| {
+		    let w = dn * fact[i] * fact[n-1-i];
+		    if (n - 1 - i) % 2 == 0 { w } else { -w }
+		}).collect()
+	    } else {
+		tree[1].deriv().multipoint_eval(&xs)
+	    };
+	    let sz = tree.len() / 2;
+	    let mut p = vec![Polynomial::new(); 2 * sz];
+	    for i in 0..n { p[sz + i] = Polynomial::from(vec![points[i].1 / w[i]]); }
+	    for v in (1..sz).rev() {
+		p[v] = &(&p[2*v] * &tree[2*v+1]) + &(&p[2*v+1] * &tree[2*v]);
+	    }
+	    p.swap_remove(1)
+	}
+
+	// Monic; gcd(0, 0) = 0.
+	pub fn gcd(&self, other: &Polynomial<Mint>) -> Polynomial<Mint> {
+	    let mut p = [Polynomial::from(self.0.clone()), Polynomial::from(other.0.clone())];
+	    if p[0].0.len() < p[1].0.len() { p.swap(0, 1); }
+	    while !p[1].0.is_empty() {
+		if p[0].0.len() > HALF_GCD_LEN {
+		    p = apply(&half_gcd(&p), &p);
+		    if p[1].0.is_empty() { break; }
+		}
+		let r = &p[0] % &p[1];
+		p = [std::mem::replace(&mut p[1], Polynomial::new()), r];
+	    }
+	    let mut g = std::mem::replace(&mut p[0], Polynomial::new());
+	    if let Some(&c) = g.0.last() { g *= c.inv(); }
+	    g
+	}
+    }
+
+    // tree[v] = prod (x - x_i) over the leaves i below v; leaves start at tree.len() / 2.
+    fn subproduct_tree<Mint: NttFriendly>(xs: &[Mint]) -> Vec<Polynomial<Mint>> {
+	let sz = xs.len().next_power_of_two();
+	let mut tree = vec![Polynomial::from(vec![Mint::ONE]); 2 * sz];
+	for i in 0..xs.len() { tree[sz + i] = Polynomial::from(vec![-xs[i], Mint::ONE]); }
+	for v in (1..sz).rev() { tree[v] = &tree[2*v] * &tree[2*v+1]; }
+	tree
     }
 
-    impl Clone for Polynomial {
-	fn clone(&self) -> Polynomial {
+    // ys[i] = g(i) for i < ys.len() <= 2^j, where deg g < 2^j.
+    // fall[j] = (t (t - 1) ... (t - 2^j + 1), 1 / its reverse mod x^(2^j)).
+    fn eval_consecutive<Mint: NttFriendly>(g: Polynomial<Mint>, fall: &[(Polynomial<Mint>, Polynomial<Mint>)], j: usize, ys: &mut [Mint]) {
+	if ys.is_empty() { return; }
+	let n = 1 << j;
+	if n <= NAIVE_LEN {
+	    for (i, y) in ys.iter_mut().enumerate() { *y = g.eval(Mint::from(i as i64)); }
+	    return;
+	}
+	let (f, f_inv) = &fall[j-1];
+	let rem = |g: &Polynomial<Mint>| {
+	    if g.0.len() < f.0.len() { return Polynomial::from(g.0.clone()); }
+	    let k = g.0.len() - f.0.len() + 1;
+	    let mut q = (&g.reversed().mod_xk(k) * &f_inv.mod_xk(k)).mod_xk(k).0;
+	    q.resize(k, Mint::ZERO);
+	    q.reverse();
+	    g - &(f * &Polynomial::from(q))
+	};
+	let (l, r) = ys.split_at_mut((n / 2).min(ys.len()));
+	if !r.is_empty() {
+	    // g(n/2 + t) for t < n/2
+	    let shifted = g.taylor_shift(Mint::from((n / 2) as i64));
+	    eval_consecutive(rem(&shifted), fall, j - 1, r);
+	}
+	eval_consecutive(rem(&g), fall, j - 1, l);
+    }
+
+    // r = f mod tree[v], where v covers the leaves lo..hi.
+    fn descend<Mint: NttFriendly>(tree: &[Polynomial<Mint>], v: usize, r: Polynomial<Mint>, lo: usize, hi: usize, xs: &[Mint], ys: &mut [Mint]) {
+	if lo >= xs.len() { return; }
+	if hi - lo <= NAIVE_LEN {
+	    for i in lo..hi.min(xs.len()) { ys[i] = r.eval(xs[i]); }
+	    return;
+	}
+	let mid = (lo + hi) / 2;
+	descend(tree, 2 * v, &r % &tree[2*v], lo, mid, xs, ys);
+	descend(tree, 2 * v + 1, &r % &tree[2*v+1], mid, hi, xs, ys);
+    }
+
+    // (c, l): c_0 = 1 and sum_{i <= l} c_i a_{n-i} = 0 for all l <= n < a.len(), with l minimal.
+    fn berlekamp_massey_impl<Mint: NttFriendly>(a: &[Mint]) -> (Vec<Mint>, usize) {
+	let (mut c, mut b) = (vec![Mint::ONE], vec![Mint::ONE]);
+	let (mut l, mut m, mut bd) = (0, 1, Mint::ONE);
+	for n in 0..a.len() {
+	    let d = (1..=l).fold(a[n], |d, i| d + c[i] * a[n-i]);
+	    if d == Mint::ZERO {
+		m += 1;
+		continue;
+	    }
+	    let coef = d / bd;
+	    let t = c.clone();
+	    if c.len() < b.len() + m { c.resize(b.len() + m, Mint::ZERO); }
+	    for i in 0..b.len() { c[i+m] -= coef * b[i]; }
+	    if 2 * l <= n {
+		l = n + 1 - l;
+		b = t;
+		bd = d;
+		m = 1;
+	    } else {
+		m += 1;
+	    }
+	}
+	c.resize(l + 1, Mint::ZERO);
+	(c, l)
+    }
+
+    // The shortest recurrence sum_i c_i a_{n-i} = 0 as c(x) with c_0 = 1; a needs twice its order terms.
+    pub fn berlekamp_massey<Mint: NttFriendly>(a: &[Mint]) -> Polynomial<Mint> {
+	Polynomial::from(berlekamp_massey_impl(a).0)
+    }
+
+    // [x^n] p(x) / q(x) by Bostan-Mori; q_0 != 0.
+    pub fn nth_term_of_rational<Mint: NttFriendly>(p: &Polynomial<Mint>, q: &Polynomial<Mint>, mut n: u64) -> Mint {
+	assert!(q.get(0) != Mint::ZERO, "ERROR: nth_term_of_rational q(0) = 0");
+	let (mut p, mut q) = (p.clone(), q.clone());
+	while n > 0 {
+	    let mut q_neg = q.clone();
+	    q_neg.0.iter_mut().skip(1).step_by(2).for_each(|c| *c = -*c);
+	    let u = &p * &q_neg;
+	    let v = &q * &q_neg;
+	    p = Polynomial::from(u.0.into_iter().skip((n % 2) as usize).step_by(2).collect());
+	    q = Polynomial::from(v.0.into_iter().step_by(2).collect());
+	    n /= 2;
+	}
+	p.get(0) / q.get(0)
+    }
+
+    // a_n of the shortest linear recurrence that fits a.
+    pub fn nth_term_of_recurrence<Mint: NttFriendly>(a: &[Mint], n: u64) -> Mint {
+	if n < a.len() as u64 { return a[n as usize]; }
+	let (c, l) = berlekamp_massey_impl(a);
+	let q = Polynomial::from(c);
+	let p = (&Polynomial::from(a[..l].to_vec()) * &q).mod_xk(l);
+	nth_term_of_rational(&p, &q, n)
+    }
+
+    // Below this length gcd runs plain Euclid steps.
+    const HALF_GCD_LEN: usize = 64;
+
+    type Matrix2<Mint> = [[Polynomial<Mint>; 2]; 2];
+
+    fn apply<Mint: NttFriendly>(m: &Matrix2<Mint>, p: &[Polynomial<Mint>; 2]) -> [Polynomial<Mint>; 2] {
+	[&(&m[0][0] * &p[0]) + &(&m[0][1] * &p[1]), &(&m[1][0] * &p[0]) + &(&m[1][1] * &p[1])]
+    }
+
+    fn compose<Mint: NttFriendly>(a: &Matrix2<Mint>, b: &Matrix2<Mint>) -> Matrix2<Mint> {
+	let f = |i: usize, j: usize| &(&a[i][0] * &b[0][j]) + &(&a[i][1] * &b[1][j]);
+	[[f(0, 0), f(0, 1)], [f(1, 0), f(1, 1)]]
+    }
+
+    // One Euclid step (p0, p1) -> (p1, p0 mod p1), recorded in m.
+    fn euclid_step<Mint: NttFriendly>(m: &mut Matrix2<Mint>, p: &mut [Polynomial<Mint>; 2]) {
+	let (q, r) = p[0].div_rem(&p[1]);
+	let row = [&m[0][0] - &(&q * &m[1][0]), &m[0][1] - &(&q * &m[1][1])];
+	m.swap(0, 1);
+	m[1] = row;
+	*p = [std::mem::replace(&mut p[1], Polynomial::new()), r];
+    }
+
+    // m with m * p = (a, b), len a > ceil(len p0 / 2) >= len b, where len is the number of coefficients.
+    fn half_gcd<Mint: NttFriendly>(p: &[Polynomial<Mint>; 2]) -> Matrix2<Mint> {
+	let one = Polynomial::from(vec![Mint::ONE]);
+	let id = [[one.clone(), Polynomial::new()], [Polynomial::new(), one]];
+	let k = (p[0].0.len() + 1) / 2;
+	if p[1].0.len() <= k { return id; }
+	let mut m = half_gcd(&[p[0].substr(k, usize::MAX), p[1].substr(k, usize::MAX)]);
+	let mut p = apply(&m, p);
+	if p[1].0.len() <= k { return m; }
+	euclid_step(&mut m, &mut p);
+	if p[1].0.len() <= k { return m; }
+	let j = 2 * k - (p[0].0.len() - 1);
+	compose(&half_gcd(&[p[0].substr(j, usize::MAX), p[1].substr(j, usize::MAX)]), &m)
+    }
+
+    impl<Mint: NttFriendly> Clone for Polynomial<Mint> {
+	fn clone(&self) -> Polynomial<Mint> {
 	    Self(self.0.to_vec())
 	}
     }
 
-    impl Add for Polynomial {
-	type Output = Polynomial;
-	fn add(self, other: Self) -> Polynomial {
+    impl<Mint: NttFriendly> Add for Polynomial<Mint> {
+	type Output = Polynomial<Mint>;
+	fn add(self, other: Self) -> Polynomial<Mint> {
 	    &self + &other
 	}
     }
 
-    impl Add for &Polynomial {
-	type Output = Polynomial;
-	fn add(self, other: Self) -> Polynomial {
+    impl<Mint: NttFriendly> Add for &Polynomial<Mint> {
+	type Output = Polynomial<Mint>;
+	fn add(self, other: Self) -> Polynomial<Mint> {
 	    let mut v = vec![Mint::ZERO; self.0.len().max(other.0.len())];
 	    for i in 0..self.0.len() { v[i] = self.0[i]; }
 	    for i in 0..other.0.len() { v[i] += other.0[i]; }
@@ -180,23 +556,23 @@ mod polynomial {
 	}
     }
 
-    impl AddAssign<&Polynomial> for Polynomial {
+    impl<Mint: NttFriendly> AddAssign<&Polynomial<Mint>> for Polynomial<Mint> {
 	fn add_assign(&mut self, other: &Self) {
 	    while self.0.len() < other.0.len() { self.0.push(Mint::ZERO); }
 	    for i in 0..other.0.len() { self.0[i] += other.0[i]; }
 	}
     }
 
-    impl Sub for Polynomial {
-	type Output = Polynomial;
-	fn sub(self, other: Self) -> Polynomial {
+    impl<Mint: NttFriendly> Sub for Polynomial<Mint> {
+	type Output = Polynomial<Mint>;
+	fn sub(self, other: Self) -> Polynomial<Mint> {
 	    &self - &other
 	}
     }
 
-    impl Sub for &Polynomial {
-	type Output = Polynomial;
-	fn sub(self, other: Self) -> Polynomial {
+    impl<Mint: NttFriendly> Sub for &Polynomial<Mint> {
+	type Output = Polynomial<Mint>;
+	fn sub(self, other: Self) -> Polynomial<Mint> {
 	    let mut v = vec![Mint::ZERO; self.0.len().max(other.0.len())];
 	    for i in 0..self.0.len() { v[i] = self.0[i]; }
 	    for i in 0..other.0.len() { v[i] -= other.0[i]; }
@@ -204,43 +580,43 @@ mod polynomial {
 	}
     }
 
-    impl SubAssign<&Polynomial> for Polynomial {
+    impl<Mint: NttFriendly> SubAssign<&Polynomial<Mint>> for Polynomial<Mint> {
 	fn sub_assign(&mut self, other: &Self) {
 	    while self.0.len() < other.0.len() { self.0.push(Mint::ZERO); }
 	    for i in 0..other.0.len() { self.0[i] -= other.0[i]; }
 	}
     }
 
-    impl Mul for Polynomial {
-	type Output = Polynomial;
-	fn mul(self, other: Self) -> Polynomial {
+    impl<Mint: NttFriendly> Mul for Polynomial<Mint> {
+	type Output = Polynomial<Mint>;
+	fn mul(self, other: Self) -> Polynomial<Mint> {
 	    convolution(&self, &other)
 	}
     }
 
-    impl Mul for &Polynomial {
-	type Output = Polynomial;
-	fn mul(self, other: Self) -> Polynomial {
+    impl<Mint: NttFriendly> Mul for &Polynomial<Mint> {
+	type Output = Polynomial<Mint>;
+	fn mul(self, other: Self) -> Polynomial<Mint> {
 	    convolution(self, other)
 	}
     }
 
-    impl MulAssign<&Polynomial> for Polynomial {
+    impl<Mint: NttFriendly> MulAssign<&Polynomial<Mint>> for Polynomial<Mint> {
 	fn mul_assign(&mut self, other: &Self) {
 	    *self = convolution(self, other);
 	}
     }
 
-    impl Mul<Mint> for Polynomial {
-	type Output = Polynomial;
+    impl<Mint: NttFriendly> Mul<Mint> for Polynomial<Mint> {
+	type Output = Polynomial<Mint>;
 	fn mul(self, other: Mint) -> Self {
 	    &self * other
 	}
     }
 
-    impl Mul<Mint> for &Polynomial {
-	type Output = Polynomial;
-	fn mul(self, other: Mint) -> Polynomial {
+    impl<Mint: NttFriendly> Mul<Mint> for &Polynomial<Mint> {
+	type Output = Polynomial<Mint>;
+	fn mul(self, other: Mint) -> Polynomial<Mint> {
 	    if other == Mint::ZERO {
 		Polynomial::new()
 	    } else {
@@ -249,71 +625,207 @@ mod polynomial {
 	}
     }
 
-    impl MulAssign<Mint> for Polynomial {
+    impl<Mint: NttFriendly> MulAssign<Mint> for Polynomial<Mint> {
 	fn mul_assign(&mut self, other: Mint) {
 	    *self = &*self * other;
 	}
     }
 
-    impl Neg for Polynomial {
+    impl<Mint: NttFriendly> Div for Polynomial<Mint> {
+	type Output = Polynomial<Mint>;
+	fn div(self, other: Self) -> Polynomial<Mint> {
+	    self.div_rem(&other).0
+	}
+    }
+
+    impl<Mint: NttFriendly> Div for &Polynomial<Mint> {
+	type Output = Polynomial<Mint>;
+	fn div(self, other: Self) -> Polynomial<Mint> {
+	    self.div_rem(other).0
+	}
+    }
+
+    impl<Mint: NttFriendly> Rem for Polynomial<Mint> {
+	type Output = Polynomial<Mint>;
+	fn rem(self, other: Self) -> Polynomial<Mint> {
+	    self.div_rem(&other).1
+	}
+    }
+
+    impl<Mint: NttFriendly> Rem for &Polynomial<Mint> {
+	type Output = Polynomial<Mint>;
+	fn rem(self, other: Self) -> Polynomial<Mint> {
+	    self.div_rem(other).1
+	}
+    }
+
+    impl<Mint: NttFriendly> Neg for Polynomial<Mint> {
 	type Output = Self;
 	fn neg(self) -> Self {
 	    Polynomial::from(self.0.iter().map(|&x| -x).collect())
 	}
     }
 
-    pub fn ntt_impl(x: &mut Vec<Mint>, e: &Vec<Mint>) {
-	assert!((Mint::MOD - 1) % x.len() as u64 == 0);
-	let n = x.len();
-	let y = &mut vec![Mint::ZERO; n];
-	let mut nn = n;
-	let mut s = 1;
-	while nn > 1 {
-	    let m = nn / 2;
-	    for p in 0..m {
-		let wp = e[p*(n/nn)];
-		for q in 0..s {
-		    let a = x[q+s*p];
-		    let b = x[q+s*(p+m)];
-		    y[q+s*(p+p)] = a + b;
-		    y[q+s*(p+p+1)] = (a - b) * wp;
+    // Powers of a primitive len-th root of unity, and of its inverse, for i < len / 2.
+    struct Roots<Mint> {
+	w: Vec<Mint>,
+	iw: Vec<Mint>,
+    }
+
+    thread_local! {
+	static ROOTS: RefCell<HashMap<(TypeId, u64), Rc<dyn Any>>> = RefCell::new(HashMap::new());
+    }
+
+    // Cached per modulus; rebuilt only when a longer transform is needed.
+    fn roots<Mint: NttFriendly>(len: usize) -> Rc<Roots<Mint>> {
+	let key = (TypeId::of::<Mint>(), Mint::modulus());
+	if let Some(r) = ROOTS.with(|c| c.borrow().get(&key).cloned()) {
+	    let r = r.downcast::<Roots<Mint>>().unwrap();
+	    if 2 * r.w.len() >= len { return r; }
+	}
+	let g = Mint::primitive_root().pow((Mint::modulus() - 1) / len as u64);
+	let ig = g.inv();
+	let mut w = vec![Mint::ONE; len / 2];
+	let mut iw = vec![Mint::ONE; len / 2];
+	for i in 1..len / 2 {
+	    w[i] = w[i-1] * g;
+	    iw[i] = iw[i-1] * ig;
+	}
+	let r = Rc::new(Roots { w, iw });
+	ROOTS.with(|c| c.borrow_mut().insert(key, r.clone()));
+	r
+    }
+
+    // In place, radix 4 with one radix 2 pass when log2(len) is odd.
+    fn butterfly<Mint: NttFriendly>(a: &mut [Mint], inverse: bool) {
+	let n = a.len();
+	assert!(n.is_power_of_two() && Mint::max_ntt_len() % n == 0, "ERROR: NTT length {}", n);
+	if n == 1 { return; }
+	let mut j = 0;
+	for i in 1..n {
+	    let mut bit = n >> 1;
+	    while j & bit != 0 {
+		j ^= bit;
+		bit >>= 1;
+	    }
+	    j |= bit;
+	    if i < j { a.swap(i, j); }
+	}
+	let r = roots::<Mint>(n);
+	let w = if inverse { &r.iw } else { &r.w };
+	let len = 2 * w.len();
+	let mut h = 1;
+	while 4 * h <= n {
+	    // Two radix 2 passes at once; im is a primitive 4th root of unity.
+	    let im = w[len / 4];
+	    let step = len / (4 * h);
+	    for s in (0..n).step_by(4 * h) {
+		for j in 0..h {
+		    let w1 = w[j * step];
+		    let w2 = w1 * w1;
+		    let x0 = a[s+j];
+		    let x1 = a[s+j+h] * w2;
+		    let x2 = a[s+j+2*h] * w1;
+		    let x3 = a[s+j+3*h] * w2 * w1;
+		    let y = (x2 - x3) * im;
+		    a[s+j] = x0 + x1 + x2 + x3;
+		    a[s+j+h] = x0 - x1 + y;
+		    a[s+j+2*h] = x0 + x1 - x2 - x3;
+		    a[s+j+3*h] = x0 - x1 - y;
 		}
 	    }
-	    nn = m;
-	    s += s;
-	    std::mem::swap(x, y);
+	    h *= 4;
+	}
+	if 2 * h == n {
+	    let step = len / n;
+	    for j in 0..h {
+		let u = a[j];
+		let v = a[j+h] * w[j * step];
+		a[j] = u + v;
+		a[j+h] = u - v;
 	}
     }
+    }
 
-    fn convolution_impl(mut x: Vec<Mint>, mut y: Vec<Mint>) -> Vec<Mint> {
-	let len = {
-	    let mut a = 1;
-	    while a < x.len() + y.len() { a += a; }
-	    a
-	};
-	assert!((Mint::MOD - 1) % len as u64 == 0);
-	while x.len() < len { x.push(Mint::ZERO); }
-	while y.len() < len { y.push(Mint::ZERO); }
-
-	let rate = Mint::raw(3).pow((Mint::MOD - 1) / len as u64);
-	let rate_inv = rate.inv();
-	let mut e = vec![Mint::ONE; len];
-	let mut er = vec![Mint::ONE; len];
-	for i in 0..x.len()-1 {
-	    e[i+1] = e[i] * rate;
-	    er[i+1] = er[i] * rate_inv;
-	}
-	ntt_impl(&mut x, &e);
-	ntt_impl(&mut y, &e);
+    // a_k <- sum_i a_i g^(ik), g a primitive a.len()-th root of unity; a.len() a power of two.
+    pub fn ntt<Mint: NttFriendly>(a: &mut [Mint]) {
+	butterfly(a, false);
+    }
+
+    // Inverse of ntt.
+    pub fn intt<Mint: NttFriendly>(a: &mut [Mint]) {
+	butterfly(a, true);
+	let d = Mint::from(a.len() as i64).inv();
+	a.iter_mut().for_each(|x| *x *= d);
+    }
+
+    // Below this length convolution_impl multiplies naively.
+    const NAIVE_LEN: usize = 32;
+
+    // Length x.len() + y.len() - 1; NTT when the length allows it, naive otherwise.
+    fn convolution_impl<Mint: NttFriendly>(mut x: Vec<Mint>, mut y: Vec<Mint>) -> Vec<Mint> {
+	if x.is_empty() || y.is_empty() {
+	    return Vec::new();
+	}
+	let n = x.len() + y.len() - 1;
+	let len = n.next_power_of_two();
+	if x.len().min(y.len()) <= NAIVE_LEN || len > Mint::max_ntt_len() {
+	    let mut z = vec![Mint::ZERO; n];
+	    for i in 0..x.len() {
+		for j in 0..y.len() { z[i+j] += x[i] * y[j]; }
+	    }
+	    return z;
+	}
+	x.resize(len, Mint::ZERO);
+	y.resize(len, Mint::ZERO);
+	ntt(&mut x);
+	ntt(&mut y);
 	for i in 0..len { x[i] *= y[i]; }
-	ntt_impl(&mut x, &er);
-	let d = Mint::new(len as u64).inv();
-	x.iter_mut().for_each(|a| *a *= d);
-	while !x.is_empty() && x.last().unwrap().geti() == 0 { x.pop(); }
+	intt(&mut x);
+	x.truncate(n);
 	x
     }
 
-    fn convolution(x: &Polynomial, y: &Polynomial) -> Polynomial {
+    const P1: u64 = 754974721;
+    const P2: u64 = 167772161;
+    const P3: u64 = 469762049;
+
+    // The convolution mod P1, P2 and P3 of a and b given as residues.
+    fn convolution_three_primes(a: &[i128], b: &[i128]) -> Vec<[(u64, u64); 3]> {
+	fn conv<const M: u64>(a: &[i128], b: &[i128]) -> Vec<u64> {
+	    let f = |v: &[i128]| v.iter().map(|&x| ModInt::<M>::new(x.rem_euclid(M as i128) as u64)).collect();
+	    convolution_impl::<ModInt<M>>(f(a), f(b)).iter().map(|x| x.getu() as u64).collect()
+	}
+	let (c1, c2, c3) = (conv::<P1>(a, b), conv::<P2>(a, b), conv::<P3>(a, b));
+	(0..c1.len()).map(|i| [(c1[i], P1), (c2[i], P2), (c3[i], P3)]).collect()
+    }
+
+    // Any modulus m < 2^31, with a.len() + b.len() <= 2^23.
+    pub fn convolution_any_mod(a: &[u64], b: &[u64], m: u64) -> Vec<u64> {
+	// with these limits every c_k < 2^23 * 2^62 < P1 P2 P3
+	assert!((1..1 << 31).contains(&m), "ERROR: convolution_any_mod modulus {}", m);
+	assert!(a.len() + b.len() <= 1 << 23, "ERROR: convolution_any_mod lengths {} + {}", a.len(), b.len());
+	let f = |v: &[u64]| v.iter().map(|&x| (x % m) as i128).collect::<Vec<_>>();
+	convolution_three_primes(&f(a), &f(b)).iter().map(|rm| garner(rm, m)).collect()
+    }
+
+    // Exact, as long as every c_k fits in i64; the result is truncated to i64 otherwise.
+    pub fn convolution_i64(a: &[i64], b: &[i64]) -> Vec<i64> {
+	let p = P1 as i128 * P2 as i128 * P3 as i128;
+	let f = |v: &[i64]| v.iter().map(|&x| x as i128).collect::<Vec<_>>();
+	convolution_three_primes(&f(a), &f(b)).iter().map(|rm| {
+	    let x = garner_u128(rm) as i128;
+	    (if x > p / 2 { x - p } else { x }) as i64
+	}).collect()
+    }
+
+    // Exact, as long as every c_k < P1 P2 P3 (about 2^85).
+    pub fn convolution_u128(a: &[u64], b: &[u64]) -> Vec<u128> {
+	let f = |v: &[u64]| v.iter().map(|&x| x as i128).collect::<Vec<_>>();
+	convolution_three_primes(&f(a), &f(b)).iter().map(|rm| garner_u128(rm)).collect()
+    }
+
+    fn convolution<Mint: NttFriendly>(x: &Polynomial<Mint>, y: &Polynomial<Mint>) -> Polynomial<Mint> {
 	if x.is_zero() || y.is_zero() {
 	    Polynomial::new()
 	} else {
@@ -322,27 +834,27 @@ mod polynomial {
     }
 
     pub mod product {
-	use super::Polynomial;
+	use super::{NttFriendly, Polynomial};
 	use std::cmp::Ordering;
 
 	#[derive(Debug)]
-	struct Entry(Polynomial);
+	struct Entry<Mint>(Polynomial<Mint>);
 
-	impl Entry {
+	impl<Mint: NttFriendly> Entry<Mint> {
 	    fn len(&self) -> usize { self.0.0.len() }
 	}
 
-	impl PartialOrd for Entry {
+	impl<Mint: NttFriendly> PartialOrd for Entry<Mint> {
 	    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
 	}
 
-	impl PartialEq for Entry {
-	    fn eq(&self, other: &Self) -> bool { false }
+	impl<Mint: NttFriendly> PartialEq for Entry<Mint> {
+	    fn eq(&self, _other: &Self) -> bool { false }
 	}
 
-	impl Eq for Entry {}
+	impl<Mint: NttFriendly> Eq for Entry<Mint> {}
 
-	impl Ord for Entry {
+	impl<Mint: NttFriendly> Ord for Entry<Mint> {
 	    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
 		// Reversed;
 		other.len().cmp(&self.len()) 
@@ -350,20 +862,20 @@ mod polynomial {
 	}
 
 	#[derive(Debug)]
-	pub struct Product {
-	    heap: std::collections::BinaryHeap::<Entry>,
+	pub struct Product<Mint> {
+	    heap: std::collections::BinaryHeap::<Entry<Mint>>,
 	}
 
-	impl Product {
+	impl<Mint: NttFriendly> Product<Mint> {
 	    pub fn new() -> Self {
 		Self { heap: std::collections::BinaryHeap::new() }
 	    }
 
-	    pub fn push(&mut self, p: Polynomial) {
+	    pub fn push(&mut self, p: Polynomial<Mint>) {
 		self.heap.push(Entry(p));
 	    }
 
-	    pub fn product_mod_xk(&mut self, k: usize) -> Polynomial {
+	    pub fn product_mod_xk(&mut self, k: usize) -> Polynomial<Mint> {
 		loop {
 		    if let Some(p) = self.heap.pop() {
 			if let Some(q) = self.heap.pop() {
@@ -373,7 +885,7 @@ mod polynomial {
 			}
 		    } else {
 			let mut p = Polynomial::new();
-			p.set(0, super::Mint::ONE);
+			p.set(0, Mint::ONE);
 			p.mod_xk(k);
 			return p;
 		    }
@@ -381,4 +893,431 @@ mod polynomial {
 	    }
 	}
     }
+
+    // First terms of common counting sequences.
+    pub mod sequences {
+	use super::{NttFriendly, Polynomial};
+	use crate::mod_int::Fact;
+
+	fn from_egf<Mint: NttFriendly>(f: &Polynomial<Mint>, n: usize, fact: &Fact<Mint>) -> Vec<Mint> {
+	    (0..n).map(|i| f.get(i) * fact.fact(i)).collect()
+	}
+
+	// e^x - 1, up to x^(n - 1)
+	fn exp_minus_one<Mint: NttFriendly>(n: usize, fact: &Fact<Mint>) -> Polynomial<Mint> {
+	    Polynomial::from((0..n).map(|i| if i == 0 { Mint::ZERO } else { fact.fact_inv(i) }).collect())
+	}
+
+	// p(0), ..., p(n - 1): prod 1 / (1 - x^k) = exp(sum_m sigma(m) / m x^m)
+	pub fn partitions<Mint: NttFriendly>(n: usize) -> Vec<Mint> {
+	    let fact = Fact::<Mint>::new(n + 1);
+	    let mut g = vec![Mint::ZERO; n];
+	    for j in 1..n {
+		for m in (j..n).step_by(j) { g[m] += fact.inv(j); }
+	    }
+	    let p = Polynomial::from(g).exp(n);
+	    (0..n).map(|i| p.get(i)).collect()
+	}
+
+	// B_0, ..., B_(n - 1) with B_1 = -1/2: x / (e^x - 1) = sum B_k x^k / k!
+	pub fn bernoulli<Mint: NttFriendly>(n: usize) -> Vec<Mint> {
+	    let fact = Fact::<Mint>::new(n + 2);
+	    let f = Polynomial::from((0..n).map(|i| fact.fact_inv(i + 1)).collect());
+	    from_egf(&f.inv(n), n, &fact)
+	}
+
+	// Unsigned [n k] for k = 0..=n: x (x + 1) ... (x + n - 1)
+	pub fn stirling1_row<Mint: NttFriendly>(n: usize) -> Vec<Mint> {
+	    let mut f = Polynomial::from(vec![Mint::ONE]);
+	    let mut m = 0;
+	    for b in (0..usize::BITS - n.leading_zeros()).rev() {
+		// f = x (x + 1) ... (x + m - 1)
+		f = &f * &f.taylor_shift(Mint::from(m as i64));
+		m *= 2;
+		if n >> b & 1 == 1 {
+		    f = &f * &Polynomial::from(vec![Mint::from(m as i64), Mint::ONE]);
+		    m += 1;
+		}
+	    }
+	    (0..=n).map(|k| f.get(k)).collect()
+	}
+
+	// Unsigned [i k] for i = 0..n: (-log(1 - x))^k / k!
+	pub fn stirling1_column<Mint: NttFriendly>(n: usize, k: usize) -> Vec<Mint> {
+	    let fact = Fact::<Mint>::new(n.max(k) + 1);
+	    let l = Polynomial::from((0..n).map(|i| if i == 0 { Mint::ZERO } else { fact.inv(i) }).collect());
+	    from_egf(&(l.pow(k as u64, n) * fact.fact_inv(k)), n, &fact)
+	}
+
+	// S(n, k) for k = 0..=n: sum_i (-1)^(k - i) i^n / (i! (k - i)!)
+	pub fn stirling2_row<Mint: NttFriendly>(n: usize) -> Vec<Mint> {
+	    let fact = Fact::<Mint>::new(n + 1);
+	    let a = Polynomial::from((0..=n).map(|i| Mint::from(i as i64).pow(n as u64) * fact.fact_inv(i)).collect());
+	    let b = Polynomial::from((0..=n).map(|i| if i % 2 == 0 { fact.fact_inv(i) } else { -fact.fact_inv(i) }).collect());
+	    let c = &a * &b;
+	    (0..=n).map(|k| c.get(k)).collect()
+	}
+
+	// S(i, k) for i = 0..n: (e^x - 1)^k / k!
+	pub fn stirling2_column<Mint: NttFriendly>(n: usize, k: usize) -> Vec<Mint> {
+	    let fact = Fact::<Mint>::new(n.max(k) + 1);
+	    from_egf(&(exp_minus_one(n, &fact).pow(k as u64, n) * fact.fact_inv(k)), n, &fact)
+	}
+
+	// Bell_0, ..., Bell_(n - 1): exp(e^x - 1)
+	pub fn bell<Mint: NttFriendly>(n: usize) -> Vec<Mint> {
+	    let fact = Fact::<Mint>::new(n + 1);
+	    from_egf(&exp_minus_one(n, &fact).exp(n), n, &fact)
+	}
+
+	// A(n, k) for k = 0..n, permutations of n with k ascents: sum_j (-1)^j C(n + 1, j) (k + 1 - j)^n
+	pub fn eulerian<Mint: NttFriendly>(n: usize) -> Vec<Mint> {
+	    if n == 0 { return vec![Mint::ONE]; }
+	    let fact = Fact::<Mint>::new(n + 2);
+	    let a = Polynomial::from((0..n).map(|j| if j % 2 == 0 { fact.nck(n + 1, j) } else { -fact.nck(n + 1, j) }).collect());
+	    let b = Polynomial::from((0..n).map(|i| Mint::from(i as i64 + 1).pow(n as u64)).collect());
+	    let c = &a * &b;
+	    (0..n).map(|k| c.get(k)).collect()
+	}
+    }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+	use crate::mod_int::ModInt998244353;
+	use crate::stress::XorShift;
+	type Mint = ModInt998244353;
+
+	fn sample<Mint: NttFriendly>(n: usize, seed: u64) -> Vec<Mint> {
+	    let mut rng = XorShift::new(seed);
+	    (0..n).map(|_| Mint::from((rng.next_u64() >> 2) as i64)).collect()
+	}
+
+	fn naive<Mint: NttFriendly>(a: &[Mint], b: &[Mint]) -> Polynomial<Mint> {
+	    let mut c = vec![Mint::ZERO; a.len() + b.len()];
+	    for i in 0..a.len() {
+		for j in 0..b.len() {
+		    c[i + j] += a[i] * b[j];
+		}
+	    }
+	    Polynomial::from(c)
+	}
+
+	#[test]
+	fn mul() {
+	    for (n, m) in [(1, 1), (3, 5), (17, 32), (40, 1), (33, 33), (64, 64), (100, 70), (200, 57)] {
+		let (a, b) = (sample::<Mint>(n, 1 + n as u64), sample::<Mint>(m, 7 + m as u64));
+		let c = Polynomial::from(a.clone()) * Polynomial::from(b.clone());
+		assert_eq!(c.0, naive(&a, &b).0);
+	    }
+	}
+
+	#[test]
+	fn div_rem_eval() {
+	    for (n, m) in [(0, 1), (1, 1), (5, 3), (3, 5), (40, 7), (150, 80), (300, 1)] {
+		let a = Polynomial::from(sample::<Mint>(n, 5 + n as u64));
+		let b = Polynomial::from(sample::<Mint>(m, 9 + m as u64));
+		let (q, r) = a.div_rem(&b);
+		assert!(r.0.len() < b.0.len());
+		assert_eq!((&(&q * &b) + &r).0, a.0);
+		assert_eq!((&a / &b).0, q.0);
+		assert_eq!((a.clone() % b.clone()).0, r.0);
+		let x = Mint::new(12345);
+		let direct = (0..n).fold(Mint::ZERO, |s, i| s + a.get(i) * x.pow(i as u64));
+		assert_eq!(a.eval(x), direct);
+	    }
+	}
+
+	#[test]
+	fn multipoint_interpolate() {
+	    for n in [0, 1, 2, 5, 33, 100, 257] {
+		let f = Polynomial::from(sample::<Mint>(n, 11 + n as u64));
+		let xs = sample::<Mint>(n + 3, 13 + n as u64);
+		let ys = f.multipoint_eval(&xs);
+		assert_eq!(ys, xs.iter().map(|&x| f.eval(x)).collect::<Vec<_>>());
+		let points: Vec<_> = xs[..n].iter().copied().zip(ys.iter().copied()).collect();
+		assert_eq!(Polynomial::interpolate(&points).0, f.0);
+
+		// x_i = 5 - 3 i
+		let xs: Vec<_> = (0..n).map(|i| Mint::new(5) - Mint::new(3 * i as u64)).collect();
+		let points: Vec<_> = xs.iter().map(|&x| (x, f.eval(x))).collect();
+		assert_eq!(Polynomial::interpolate(&points).0, f.0);
+
+		// evaluation on arithmetic progressions, with fewer and with more points than coefficients
+		for (m, a, d) in [(n, 5, 3), (n + 40, 7, 1), (n / 2 + 33, 998244350, 2), (3 * n + 50, 0, 12345)] {
+		    let xs: Vec<_> = (0..m).map(|i| Mint::new(a) + Mint::new(d) * Mint::from(i as i64)).collect();
+		    assert_eq!(f.multipoint_eval(&xs), xs.iter().map(|&x| f.eval(x)).collect::<Vec<_>>());
+		}
+	    }
+	}
+
+	#[test]
+	fn sqrt() {
+	    for (n, t) in [(1, 0), (10, 0), (40, 4), (100, 2)] {
+		let g = Polynomial::from(sample::<Mint>(n, 17 + n as u64));
+		let f = (&g * &g).mul_xk(t);
+		for k in [1, 5, 37, 150] {
+		    let h = f.sqrt(k).unwrap();
+		    assert_eq!(Polynomial::from((&h * &h).mod_xk(k).0).0, Polynomial::from(f.mod_xk(k).0).0);
+		}
+	    }
+	    let x = Polynomial::from(vec![Mint::ZERO, Mint::ONE]);
+	    assert!(x.sqrt(5).is_none());
+	    assert!(Polynomial::from(vec![Mint::primitive_root()]).sqrt(5).is_none());
+	    assert!(Polynomial::<Mint>::new().sqrt(5).unwrap().is_zero());
+	    assert!(x.mul_xk(9).sqrt(3).unwrap().is_zero());
+	}
+
+	#[test]
+	fn taylor_shift_compose() {
+	    for n in [0, 1, 4, 50] {
+		let f = Polynomial::from(sample::<Mint>(n, 19 + n as u64));
+		let (a, b) = (Mint::new(7), Mint::new(123));
+		let g = f.taylor_shift(b);
+		let h = f.compose_with_linear(a, b);
+		for x in sample::<Mint>(5, 23) {
+		    assert_eq!(g.eval(x), f.eval(x + b));
+		    assert_eq!(h.eval(x), f.eval(a * x + b));
+		}
+	    }
+	}
+
+	#[test]
+	fn shift_of_sampling_points() {
+	    type M = ModInt<97>;
+	    for n in [0, 1, 3, 10, 40] {
+		let f = Polynomial::from(sample::<M>(n, 29 + n as u64));
+		let ys: Vec<M> = (0..n).map(|i| f.eval(M::new(i as u64))).collect();
+		for m in [0, 2, 10, 50, 90, 1000] {
+		    let cnt = 70;
+		    let r = Polynomial::shift_of_sampling_points(&ys, m, cnt);
+		    assert_eq!(r, (0..cnt).map(|k| f.eval(M::new(m + k as u64))).collect::<Vec<_>>());
+		}
+	    }
+	    let f = Polynomial::from(sample::<Mint>(100, 31));
+	    let ys: Vec<Mint> = (0..100).map(|i| f.eval(Mint::new(i))).collect();
+	    let m = 1_000_000_000_000u64;
+	    let r = Polynomial::shift_of_sampling_points(&ys, m, 50);
+	    assert_eq!(r, (0..50).map(|k| f.eval(Mint::new(m + k))).collect::<Vec<_>>());
+
+	    // a modulus above 2^63
+	    type Big = ModInt64<9223372036854775837>;
+	    let f = Polynomial::from(vec![Big::ONE, Big::ZERO, Big::ONE]);
+	    let ys: Vec<Big> = (0..3).map(|i| f.eval(Big::new(i))).collect();
+	    let r = Polynomial::shift_of_sampling_points(&ys, (1 << 63) + 10, 3);
+	    assert_eq!(r, [362, 325, 290].map(Big::new));
+	    let f = Polynomial::from(sample::<Big>(40, 37));
+	    let ys: Vec<Big> = (0..40).map(|i| f.eval(Big::new(i))).collect();
+	    let m = Big::modulus() - 20;
+	    let r = Polynomial::shift_of_sampling_points(&ys, m, 70);
+	    assert_eq!(r, (0..70).map(|k| f.eval(Big::new(m) + Big::new(k))).collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn linear_recurrence() {
+	    // fibonacci
+	    let fib: Vec<Mint> = (0..20).scan((Mint::ZERO, Mint::ONE), |s, _| { let r = s.0; *s = (s.1, s.0 + s.1); Some(r) }).collect();
+	    assert_eq!(berlekamp_massey(&fib).0, vec![Mint::ONE, -Mint::ONE, -Mint::ONE]);
+	    assert_eq!(nth_term_of_recurrence(&fib, 19), fib[19]);
+	    assert_eq!(nth_term_of_recurrence(&fib, 90), Mint::new(2880067194370816120 % 998244353));
+	    assert_eq!(nth_term_of_recurrence(&[Mint::ZERO, Mint::ZERO, Mint::ONE, Mint::ZERO, Mint::ZERO, Mint::ONE], 8), Mint::ONE);
+
+	    for d in [1, 3, 10, 40] {
+		let c = sample::<Mint>(d, 37 + d as u64);
+		let mut a = sample::<Mint>(d, 41 + d as u64);
+		for n in d..3 * d + 100 {
+		    let v = (0..d).fold(Mint::ZERO, |s, i| s + c[i] * a[n-1-i]);
+		    a.push(v);
+		}
+		let q = berlekamp_massey(&a[..2 * d]);
+		assert!(q.0.len() <= d + 1);
+		for n in [0, 1, 2 * d, 3 * d + 50, 3 * d + 99] {
+		    assert_eq!(nth_term_of_recurrence(&a[..2 * d], n as u64), a[n]);
+		}
+	    }
+
+	    // 1 / (1 - x)^2 = sum (n + 1) x^n
+	    let q = Polynomial::from(vec![Mint::ONE, -Mint::new(2), Mint::ONE]);
+	    let p = Polynomial::from(vec![Mint::ONE]);
+	    assert_eq!(nth_term_of_rational(&p, &q, 1_000_000_000_000_000_000), Mint::new(1_000_000_000_000_000_001));
+	}
+
+	#[test]
+	fn sequences() {
+	    use super::sequences::*;
+	    let n = 30;
+	    let m = |v: i64| Mint::from(v);
+	    let mut p = vec![vec![Mint::ZERO; n]; n];
+	    // p[i][j]: partitions of i into parts <= j
+	    for i in 0..n {
+		for j in 0..n {
+		    p[i][j] = if i == 0 { Mint::ONE } else if j == 0 { Mint::ZERO } else { p[i][j - 1] + if i >= j { p[i - j][j] } else { Mint::ZERO } };
+		}
+	    }
+	    assert_eq!(partitions::<Mint>(n), (0..n).map(|i| p[i][n - 1]).collect::<Vec<_>>());
+
+	    let (mut s1, mut s2, mut eu) = (vec![vec![Mint::ZERO; n + 1]; n + 1], vec![vec![Mint::ZERO; n + 1]; n + 1], vec![vec![Mint::ZERO; n + 1]; n + 1]);
+	    s1[0][0] = Mint::ONE;
+	    s2[0][0] = Mint::ONE;
+	    eu[0][0] = Mint::ONE;
+	    for i in 1..=n {
+		for k in 0..=i {
+		    let prev = |t: &Vec<Vec<Mint>>, k: usize| if k < i { t[i - 1][k] } else { Mint::ZERO };
+		    let left = |t: &Vec<Vec<Mint>>| if k > 0 { t[i - 1][k - 1] } else { Mint::ZERO };
+		    s1[i][k] = left(&s1) + m(i as i64 - 1) * prev(&s1, k);
+		    s2[i][k] = left(&s2) + m(k as i64) * prev(&s2, k);
+		    eu[i][k] = m(k as i64 + 1) * prev(&eu, k) + m((i - k) as i64) * left(&eu);
+		}
+	    }
+	    for i in 0..=n {
+		assert_eq!(stirling1_row::<Mint>(i), s1[i][..=i].to_vec());
+		assert_eq!(stirling2_row::<Mint>(i), s2[i][..=i].to_vec());
+		assert_eq!(eulerian::<Mint>(i), eu[i][..i.max(1)].to_vec());
+		assert_eq!(stirling1_column::<Mint>(n, i), (0..n).map(|j| s1[j][i]).collect::<Vec<_>>());
+		assert_eq!(stirling2_column::<Mint>(n, i), (0..n).map(|j| s2[j][i]).collect::<Vec<_>>());
+	    }
+	    assert_eq!(bell::<Mint>(n), (0..n).map(|i| s2[i].iter().fold(Mint::ZERO, |a, &b| a + b)).collect::<Vec<_>>());
+
+	    // sum_{j <= k} C(k + 1, j) B_j = 0 for k >= 1
+	    let b = bernoulli::<Mint>(n);
+	    assert_eq!(b[..3].to_vec(), vec![Mint::ONE, -m(1) / m(2), m(1) / m(6)]);
+	    let f = Fact::<Mint>::new(n + 2);
+	    for k in 1..n {
+		assert_eq!((0..=k).fold(Mint::ZERO, |s, j| s + f.nck(k + 1, j) * b[j]), Mint::ZERO);
+	    }
+	}
+
+	#[test]
+	fn gcd() {
+	    let euclid = |a: &Polynomial<Mint>, b: &Polynomial<Mint>| {
+		let (mut a, mut b) = (a.clone(), b.clone());
+		while !b.is_zero() {
+		    let r = &a % &b;
+		    a = b;
+		    b = r;
+		}
+		let c = a.0.last().map_or(Mint::ONE, |c| c.inv());
+		a * c
+	    };
+	    for (k, n, m) in [(0, 0, 0), (1, 3, 0), (5, 1, 1), (10, 100, 90), (70, 80, 200), (3, 250, 250), (130, 1, 140)] {
+		let g = Polynomial::from(sample::<Mint>(k, 1 + k as u64));
+		let a = &g * &Polynomial::from(sample::<Mint>(n, 2 + n as u64));
+		let b = &g * &Polynomial::from(sample::<Mint>(m, 3 + m as u64));
+		let h = a.gcd(&b);
+		assert_eq!(h.0, euclid(&a, &b).0);
+		assert_eq!(b.gcd(&a).0, h.0);
+		if !g.is_zero() && n > 0 && m > 0 { assert_eq!(h.0.len(), g.0.len()); }
+	    }
+	    let a = Polynomial::from(sample::<Mint>(200, 7));
+	    assert_eq!(a.gcd(&a.deriv()).0, vec![Mint::ONE]);
+	}
+
+	#[test]
+	fn ntt_intt() {
+	    for n in [1, 2, 4, 8, 16, 32] {
+		let a = sample::<Mint>(n, n as u64);
+		let mut b = a.clone();
+		ntt(&mut b);
+		let g = Mint::primitive_root().pow((Mint::MOD - 1) / n as u64);
+		for k in 0..n {
+		    let s = (0..n).fold(Mint::ZERO, |s, i| s + a[i] * g.pow((i * k) as u64));
+		    assert_eq!(b[k], s);
+		}
+		intt(&mut b);
+		assert_eq!(a, b);
+	    }
+	}
+
+	#[test]
+	fn convolution_three_primes() {
+	    let mut rng = XorShift::new(1);
+	    for (n, m) in [(0, 3), (1, 1), (3, 5), (17, 32), (40, 1), (100, 90)] {
+		let a: Vec<u64> = (0..n).map(|_| rng.next_u64()).collect();
+		let b: Vec<u64> = (0..m).map(|_| rng.next_u64()).collect();
+		let mut c = vec![0u128; if n == 0 || m == 0 { 0 } else { n + m - 1 }];
+		let md = 1000000007u128;
+		for i in 0..n {
+		    for j in 0..m {
+			c[i + j] = (c[i + j] + a[i] as u128 % md * (b[j] as u128 % md)) % md;
+		    }
+		}
+		assert_eq!(convolution_any_mod(&a, &b, 1000000007), c.iter().map(|&v| v as u64).collect::<Vec<_>>());
+
+		let a: Vec<i64> = a.iter().map(|&v| (v >> 28) as i64 - (1 << 35)).collect();
+		let b: Vec<i64> = b.iter().map(|&v| (v >> 43) as i64 - (1 << 20)).collect();
+		let mut c = vec![0i64; c.len()];
+		for i in 0..n {
+		    for j in 0..m { c[i + j] += a[i] * b[j]; }
+		}
+		assert_eq!(convolution_i64(&a, &b), c);
+
+		let a: Vec<u64> = a.iter().map(|&v| v.unsigned_abs()).collect();
+		let b: Vec<u64> = b.iter().map(|&v| v.unsigned_abs()).collect();
+		let mut c = vec![0u128; c.len()];
+		for i in 0..n {
+		    for j in 0..m { c[i + j] += a[i] as u128 * b[j] as u128; }
+		}
+		assert_eq!(convolution_u128(&a, &b), c);
+	    }
+	    // the largest modulus with the largest residues; (m - 1)^2 = 1 mod m
+	    let m = (1u64 << 31) - 1;
+	    let c = convolution_any_mod(&vec![m - 1; 300], &vec![m - 1; 300], m);
+	    assert_eq!(c[..300], (1..=300).collect::<Vec<u64>>()[..]);
+	}
+
+	#[test]
+	#[should_panic(expected = "convolution_any_mod modulus")]
+	fn convolution_any_mod_large_modulus() {
+	    let _ = convolution_any_mod(&[1], &[1], 1 << 31);
+	}
+
+	fn check_inv_log_exp_pow<Mint: NttFriendly>() {
+	    let k = 20;
+	    let mut a = sample::<Mint>(15, 3);
+	    a[0] = Mint::ONE;
+	    let f = Polynomial::from(a.clone());
+	    let one = Polynomial::from(vec![Mint::ONE]);
+	    assert_eq!(Polynomial::from((&f * &f.inv(k)).mod_xk(k).0).0, one.0);
+	    assert_eq!(Polynomial::from(f.log(k).exp(k).0).0, f.mod_xk(k).0);
+
+	    let mut g = one.clone();
+	    for y in 0..5 {
+		assert_eq!(f.pow(y, k).0, g.mod_xk(k).0);
+		g = &g * &f;
+	    }
+	    let h = Polynomial::from(vec![Mint::ZERO, Mint::ZERO, Mint::from(2i64), Mint::ONE]);
+	    assert_eq!(h.pow(3, 8).0, naive(&naive(&h.0, &h.0).0, &h.0).mod_xk(8).0);
+	}
+
+	#[test]
+	fn inv_log_exp_pow() {
+	    check_inv_log_exp_pow::<Mint>();
+	    check_inv_log_exp_pow::<ModInt<167772161>>();
+	    check_inv_log_exp_pow::<ModInt<1000000007>>();
+	    DynModInt::set_modulus(1000003);
+	    check_inv_log_exp_pow::<DynModInt>();
+	    assert_eq!(ModInt::<1000000007>::max_ntt_len(), 2);
+	    assert_eq!(ModInt::<754974721>::max_ntt_len(), 1 << 24);
+	    assert_eq!(ModInt::<65>::max_ntt_len(), 1);
+
+	    // exponent above 2^63 with a 64-bit modulus: (1 + x)^y = sum C(y, i) x^i
+	    type M64 = ModInt64<18446744073709551557>;
+	    let y = (1u64 << 63) + 5;
+	    let p = Polynomial::from(vec![M64::ONE, M64::ONE]).pow(y, 3);
+	    let yy = M64::from(y);
+	    assert_eq!(p.0, vec![M64::ONE, yy, yy * (yy - M64::ONE) / M64::from(2u64)]);
+	}
+
+	#[test]
+	fn product() {
+	    let mut p = product::Product::new();
+	    let mut q = Polynomial::from(vec![Mint::ONE]);
+	    for i in 1..6 {
+		let f = Polynomial::from(vec![Mint::new(i), Mint::ONE]);
+		q = &q * &f;
+		p.push(f);
+	    }
+	    assert_eq!(p.product_mod_xk(4).0, q.mod_xk(4).0);
+	}
+    }
 }
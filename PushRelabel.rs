@@ -1,4 +1,4 @@
-mod push_relabel {
+pub mod push_relabel {
     struct Edge {
 	src: usize,
 	dst: usize,
@@ -130,4 +130,33 @@ mod push_relabel {
 	    }
 	}
     }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+	use crate::stress::XorShift;
+
+	#[test]
+	fn against_min_cut() {
+	    let n = 6;
+	    let mut rng = XorShift::new(1);
+	    for _ in 0..100 {
+		let mut pr = PushRelabel::new(n);
+		let mut edges = Vec::new();
+		for _ in 0..12 {
+		    let x = rng.next_u64();
+		    let (a, b, c) = (x as usize % n, (x >> 8) as usize % n, (x >> 16) as i64 % 10);
+		    if a != b {
+			pr.add_edge(a, b, c);
+			edges.push((a, b, c));
+		    }
+		}
+		// min over the cuts with 0 on the source side and n-1 on the other.
+		let cut = (0..1 << n).filter(|s| s & 1 == 1 && s >> (n - 1) & 1 == 0).map(|s| {
+		    edges.iter().filter(|&&(a, b, _)| s >> a & 1 == 1 && s >> b & 1 == 0).map(|e| e.2).sum::<i64>()
+		}).min().unwrap();
+		assert_eq!(pr.solve(0, n - 1), cut);
+	    }
+	}
+    }
 }
@@ -1,4 +1,4 @@
-mod rangemap {
+pub mod rangemap {
     use std::fmt::Debug;
 
     #[derive(Debug, Clone)]
@@ -108,25 +108,42 @@ mod rangemap {
             acc
         }
     }
-} // mod rangemap;
 
-fn main() {
-    let mut map = rangemap::RangeMap::<i32, i32>::new_with_initial_value(-1);
-    map.overwrite(10, 20, 100);
-    map.overwrite(15, 18, 150);
-    let mut v = Vec::new();
-    {
-        map.fold_and_overwrite(10, 30, &mut v, |acc, left, right, value| {
-            println!("!! {} {} {}", left, right, value);
-            acc.push(value);
-            acc
-        }, -1);
-    }
-    println!("{:?}", v);
-    map.fold_and_overwrite(10, 30, 0, |acc, left, right, value| {
-        println!("!! {} {} {}", left, right, value);
-        value
-    }, -1);
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::stress::XorShift;
+
+        #[test]
+        fn fold_segments() {
+            let mut map = RangeMap::<i32, i32>::new_with_initial_value(-1);
+            map.overwrite(10, 20, 100);
+            map.overwrite(15, 18, 150);
+            let v = map.fold_and_overwrite(10, 30, Vec::new(), |mut acc, left, right, value| {
+                acc.push((left, right, value));
+                acc
+            }, -1);
+            assert_eq!(v, vec![(10, 15, 100), (15, 18, 150), (18, 20, 100), (20, 30, -1)]);
+            assert_eq!(map.fold(0, 40, 0, |acc, _, _, value| acc + (value != -1) as i32), 0);
+        }
 
-    let mut map = rangemap::RangeMap::<String, String>::new_with_initial_value("".to_string());
-}
+        #[test]
+        fn against_array() {
+            let n = 16;
+            let mut map = RangeMap::<usize, u64>::new();
+            let mut a = vec![0; n];
+            let mut rng = XorShift::new(1);
+            for _ in 0..300 {
+                let x = rng.next_u64();
+                let (l, r, v) = (x as usize % n, (x >> 8) as usize % n, (x >> 16) % 3);
+                let (l, r) = (l.min(r), l.max(r) + 1);
+                let sum = map.fold_and_overwrite(l, r, 0, |acc, left, right, value| acc + (right - left) as u64 * value, v);
+                assert_eq!(sum, a[l..r].iter().sum::<u64>());
+                a[l..r].iter_mut().for_each(|y| *y = v);
+                for i in 0..n {
+                    assert_eq!(map.fold(i, i + 1, 0, |_, _, _, value| value), a[i]);
+                }
+            }
+        }
+    }
+} // mod rangemap;
@@ -1,4 +1,4 @@
-mod rmq {
+pub mod rmq {
     use std::fmt::Debug;
     use std::ops::{Add, Sub, Neg, Range};
     use std::convert::From;
@@ -174,4 +174,44 @@ mod rmq {
 	    self.0.modify(i, -x);
 	}
     }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+	use crate::stress::XorShift;
+
+	#[test]
+	fn against_array() {
+	    let mut rng = XorShift::new(1);
+	    for n in 1..12 {
+		let mut a: Vec<i64> = (0..n).map(|i| (i * 7 % 5) as i64).collect();
+		let mut rmq = Rmq::from(&a);
+		let mut rxq = Rxq::from(&a);
+		for _ in 0..200 {
+		    let x = rng.next_u64();
+		    let (l, r) = (x as usize % n, (x >> 8) as usize % n);
+		    let (l, r) = (l.min(r), l.max(r) + 1);
+		    let v = (x >> 16) as i64 % 21 - 10;
+		    match x >> 40 & 3 {
+			0 => {
+			    rmq.add(l..r, v);
+			    rxq.add(l..r, v);
+			    a[l..r].iter_mut().for_each(|y| *y += v);
+			}
+			1 => {
+			    rmq.modify(l, v);
+			    rxq.modify(l, v);
+			    a[l] = v;
+			}
+			_ => {
+			    assert_eq!(rmq.min(l..r), *a[l..r].iter().min().unwrap());
+			    assert_eq!(rxq.max(l..r), *a[l..r].iter().max().unwrap());
+			    assert_eq!(rmq.get(l), a[l]);
+			    assert_eq!(rxq.get(l), a[l]);
+			}
+		    }
+		}
+	    }
+	}
+    }
 }
@@ -1,4 +1,5 @@
-mod scc {
+pub mod scc {
+    // scc[v]: component id of v; an edge v -> w implies scc[v] <= scc[w].
     pub fn scc(graph: &Vec<Vec<usize>>) -> Vec<usize> {
 	let mut vis: Vec<u8> = vec![0; graph.len()];
 	let mut ord = Vec::new();
@@ -22,11 +23,15 @@ mod scc {
 		}
 	    }
 	}
+	// Reverse post order on the reversed graph finds the components in topological order.
+	let mut rev = vec![Vec::new(); graph.len()];
+	for v in 0..graph.len() {
+	    for &w in &graph[v] { rev[w].push(v); }
+	}
 	let mut scc: Vec<usize> = vec![0; graph.len()];
 	let mut count = 0;
-	for &root in &ord {
+	for &root in ord.iter().rev() {
 	    if vis[root] == 2 { continue; }
-	    count += 1;
 	    let mut stk = Vec::new();
 	    stk.push((root, 0));
 	    while let Some(&(v, i)) = stk.last() {
@@ -34,19 +39,58 @@ mod scc {
 		    vis[v] = 2;
 		    scc[v] = count;
 		}
-		if i == graph[v].len() {
+		if i == rev[v].len() {
 		    stk.pop();
 		} else {
-		    let w = graph[v][i];
+		    let w = rev[v][i];
 		    *stk.last_mut().unwrap() = (v, i + 1);
 		    if vis[w] == 1 {
 			stk.push((w, 0));
 		    }
 		}
 	    }
+	    count += 1;
 	}
-	scc.iter_mut().for_each(|x| *x = count - *x);
 	scc
     }
-}
 
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+	use crate::stress::XorShift;
+
+	#[test]
+	fn against_reachability() {
+	    let n = 7;
+	    let mut rng = XorShift::new(1);
+	    for _ in 0..200 {
+		let mut graph = vec![Vec::new(); n];
+		let mut reach = vec![vec![false; n]; n];
+		for i in 0..n { reach[i][i] = true; }
+		for _ in 0..9 {
+		    let x = rng.next_u64();
+		    let (a, b) = (x as usize % n, (x >> 8) as usize % n);
+		    graph[a].push(b);
+		    reach[a][b] = true;
+		}
+		for k in 0..n {
+		    for i in 0..n {
+			for j in 0..n {
+			    if reach[i][k] && reach[k][j] { reach[i][j] = true; }
+			}
+		    }
+		}
+		let c = scc(&graph);
+		for a in 0..n {
+		    for b in 0..n {
+			assert_eq!(c[a] == c[b], reach[a][b] && reach[b][a]);
+			// components are numbered in topological order.
+			if reach[a][b] { assert!(c[a] <= c[b]); }
+		    }
+		}
+		let count = c.iter().max().unwrap() + 1;
+		assert!((0..count).all(|k| c.contains(&k)));
+	    }
+	}
+    }
+}
@@ -1,4 +1,4 @@
-mod segtree {
+pub mod segtree {
     use std::ops::{ Fn, RangeBounds };
     use std::clone::Clone;
 
@@ -168,4 +168,42 @@ mod segtree {
 		})
 	}
     }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+	use crate::stress::XorShift;
+
+	#[test]
+	fn range_add_range_min() {
+	    let mut rng = XorShift::new(1);
+	    for n in 1..12 {
+		let mut a: Vec<i64> = (0..n).map(|i| (i * 7 % 5) as i64).collect();
+		let mut seg = SegTreeLazy::from_vec(&a, i64::MAX / 2, 0,
+		    |x: &i64, y: &i64| *x.min(y), |x: &i64, f: &i64| x + f, |f: &i64, g: &i64| f + g);
+		for _ in 0..200 {
+		    let x = rng.next_u64();
+		    let (l, r) = (x as usize % n, (x >> 8) as usize % n);
+		    let (l, r) = (l.min(r), l.max(r) + 1);
+		    let v = (x >> 16) as i64 % 21 - 10;
+		    match x >> 40 & 3 {
+			0 => {
+			    seg.add(l..r, v);
+			    a[l..r].iter_mut().for_each(|y| *y += v);
+			}
+			1 => {
+			    assert_eq!(seg.modify(l, v), a[l]);
+			    a[l] = v;
+			}
+			_ => {
+			    assert_eq!(seg.sum(l..r), *a[l..r].iter().min().unwrap());
+			    assert_eq!(seg.sum(l..=l), a[l]);
+			    assert_eq!(seg.get(l), a[l]);
+			    assert_eq!(seg.sum(..), *a.iter().min().unwrap());
+			}
+		    }
+		}
+	    }
+	}
+    }
 }
@@ -1,4 +1,4 @@
-mod shortest_path {
+pub mod shortest_path {
     #[derive(Debug, Default)]
     pub struct ShortestPath {
 	n: usize,
@@ -41,4 +41,38 @@ mod shortest_path {
 	    Some(dist)
 	}
     }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+	use crate::stress::XorShift;
+
+	#[test]
+	fn against_bellman_ford() {
+	    let n = 6;
+	    let mut rng = XorShift::new(1);
+	    for _ in 0..200 {
+		let mut sp = ShortestPath::new(n);
+		let mut edges = Vec::new();
+		for _ in 0..10 {
+		    let x = rng.next_u64();
+		    let (a, b, c) = (x as usize % n, (x >> 8) as usize % n, (x >> 16) as i64 % 20 - 4);
+		    sp.add_edge(a, b, c);
+		    edges.push((a, b, c));
+		}
+		let mut dist = vec![i64::MAX; n];
+		dist[0] = 0;
+		let mut negative_cycle = false;
+		for round in 0..n {
+		    for &(a, b, c) in &edges {
+			if dist[a] < i64::MAX && dist[b] > dist[a] + c {
+			    dist[b] = dist[a] + c;
+			    if round == n - 1 { negative_cycle = true; }
+			}
+		    }
+		}
+		assert_eq!(sp.solve(0), if negative_cycle { None } else { Some(dist) });
+	    }
+	}
+    }
 }
@@ -0,0 +1,294 @@
+pub mod stress {
+    use std::fmt::Debug;
+
+    // xorshift64
+    #[derive(Debug, Clone)]
+    pub struct XorShift(u64);
+
+    impl XorShift {
+	// splitmix64 of the seed, so that nearby seeds give unrelated streams
+	pub fn new(seed: u64) -> Self {
+	    let mut z = seed.wrapping_add(0x9e3779b97f4a7c15);
+	    z = (z ^ z >> 30).wrapping_mul(0xbf58476d1ce4e5b9);
+	    z = (z ^ z >> 27).wrapping_mul(0x94d049bb133111eb);
+	    Self((z ^ z >> 31).max(1))
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+	    self.0 ^= self.0 << 13;
+	    self.0 ^= self.0 >> 7;
+	    self.0 ^= self.0 << 17;
+	    self.0
+	}
+
+	// [0, n)
+	pub fn usize(&mut self, n: usize) -> usize {
+	    (self.next_u64() % n as u64) as usize
+	}
+
+	// [lo, hi)
+	pub fn i64(&mut self, lo: i64, hi: i64) -> i64 {
+	    lo + (self.next_u64() % (hi - lo) as u64) as i64
+	}
+
+	pub fn bool(&mut self) -> bool {
+	    self.next_u64() >> 63 == 1
+	}
+
+	pub fn vec_i64(&mut self, n: usize, lo: i64, hi: i64) -> Vec<i64> {
+	    (0..n).map(|_| self.i64(lo, hi)).collect()
+	}
+
+	// Non-empty l..r in 0..n.
+	pub fn range(&mut self, n: usize) -> (usize, usize) {
+	    let (l, r) = (self.usize(n), self.usize(n));
+	    (l.min(r), l.max(r) + 1)
+	}
+
+	// m directed edges on n vertices, loops and multi-edges included.
+	pub fn graph(&mut self, n: usize, m: usize) -> Vec<(usize, usize)> {
+	    (0..m).map(|_| (self.usize(n), self.usize(n))).collect()
+	}
+
+	// n - 1 edges (parent, child) of a random tree, with shuffled labels.
+	pub fn tree(&mut self, n: usize) -> Vec<(usize, usize)> {
+	    let mut label: Vec<usize> = (0..n).collect();
+	    for i in (1..n).rev() {
+		let j = self.usize(i + 1);
+		label.swap(i, j);
+	    }
+	    (1..n).map(|i| (label[self.usize(i)], label[i])).collect()
+	}
+    }
+
+    pub const MAX_SIZE: usize = 30;
+
+    // Runs `iterations` random cases whose size grows from 1 to MAX_SIZE.
+    // gen(rng, size) makes a case; fast and slow must agree on every case.
+    // On a mismatch, smaller sizes are searched again and the smallest failing case is printed.
+    pub fn stress<I, O, G, F, S>(iterations: usize, mut gen: G, fast: F, slow: S)
+    where I: Debug,
+	  O: PartialEq + Debug,
+	  G: FnMut(&mut XorShift, usize) -> I,
+	  F: Fn(&I) -> O,
+	  S: Fn(&I) -> O,
+    {
+	let mut rng = XorShift::new(iterations as u64);
+	for it in 0..iterations {
+	    let size = 1 + it * MAX_SIZE / iterations.max(1);
+	    let case = gen(&mut rng, size);
+	    if fast(&case) == slow(&case) { continue; }
+	    let mut case = case;
+	    'shrink: for smaller in 1..size {
+		for _ in 0..iterations {
+		    let c = gen(&mut rng, smaller);
+		    if fast(&c) != slow(&c) {
+			case = c;
+			break 'shrink;
+		    }
+		}
+	    }
+	    let (f, s) = (fast(&case), slow(&case));
+	    eprintln!("input: {:?}", case);
+	    eprintln!("fast:  {:?}", f);
+	    eprintln!("slow:  {:?}", s);
+	    panic!("ERROR: stress test failed");
+	}
+    }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+	use crate::mod_int::{ModInt998244353, ModIntTrait};
+	use crate::polynomial::Polynomial;
+	type Mint = ModInt998244353;
+
+	#[test]
+	#[should_panic(expected = "stress test failed")]
+	fn finds_mismatch() {
+	    stress(100, |rng, size| rng.vec_i64(size, 0, 10), |a| a.iter().take(3).max().copied(), |a| a.iter().max().copied());
+	}
+
+	#[test]
+	fn tree_is_connected() {
+	    use crate::union_find::UnionFind;
+	    let mut rng = XorShift::new(1);
+	    for n in 1..50 {
+		let edges = rng.tree(n);
+		assert_eq!(edges.len(), n - 1);
+		let mut uf = UnionFind::new(n);
+		edges.iter().for_each(|&(a, b)| uf.link(a, b));
+		assert_eq!(uf.count_tree(), 1);
+	    }
+	}
+
+	// (kind, l, r, value) on an array of length n.
+	type Query = (usize, usize, usize, i64);
+
+	fn range_queries(rng: &mut XorShift, size: usize) -> (Vec<i64>, Vec<Query>) {
+	    let n = 1 + rng.usize(size);
+	    let a = rng.vec_i64(n, -10, 10);
+	    let q = (0..size * 2).map(|_| {
+		let (l, r) = rng.range(n);
+		(rng.usize(3), l, r, rng.i64(-10, 10))
+	    }).collect();
+	    (a, q)
+	}
+
+	#[test]
+	fn fenwick() {
+	    use crate::fenwick::Fenwick;
+	    stress(1000, range_queries, |(a, q)| {
+		let mut f = Fenwick::new(a.len());
+		for (i, &x) in a.iter().enumerate() { f.add(i, x); }
+		q.iter().map(|&(k, l, r, v)| if k == 0 { f.add(l, v); 0 } else { f.sum(l, r) }).collect::<Vec<_>>()
+	    }, |(a, q)| {
+		let mut a = a.clone();
+		q.iter().map(|&(k, l, r, v)| if k == 0 { a[l] += v; 0 } else { a[l..r].iter().sum() }).collect::<Vec<_>>()
+	    });
+	}
+
+	#[test]
+	fn rmq_rxq() {
+	    use crate::rmq::{Rmq, Rxq};
+	    stress(1000, range_queries, |(a, q)| {
+		let (mut rmq, mut rxq) = (Rmq::from(a), Rxq::from(a));
+		q.iter().map(|&(k, l, r, v)| match k {
+		    0 => { rmq.add(l..r, v); rxq.add(l..r, v); (0, 0) }
+		    1 => { rmq.modify(l, v); rxq.modify(l, v); (0, 0) }
+		    _ => (rmq.min(l..r), rxq.max(l..r)),
+		}).collect::<Vec<_>>()
+	    }, |(a, q)| {
+		let mut a = a.clone();
+		q.iter().map(|&(k, l, r, v)| match k {
+		    0 => { a[l..r].iter_mut().for_each(|x| *x += v); (0, 0) }
+		    1 => { a[l] = v; (0, 0) }
+		    _ => (*a[l..r].iter().min().unwrap(), *a[l..r].iter().max().unwrap()),
+		}).collect::<Vec<_>>()
+	    });
+	}
+
+	#[test]
+	fn segtree_lazy() {
+	    use crate::segtree::SegTreeLazy;
+	    // range affine (b, c): x -> b * x + c, range (sum, len)
+	    stress(1000, range_queries, |(a, q)| {
+		let init: Vec<(i64, i64)> = a.iter().map(|&x| (x, 1)).collect();
+		let mut seg = SegTreeLazy::from_vec(&init, (0, 0), (1, 0),
+		    |x: &(i64, i64), y: &(i64, i64)| (x.0 + y.0, x.1 + y.1),
+		    |x: &(i64, i64), f: &(i64, i64)| (f.0 * x.0 + f.1 * x.1, x.1),
+		    |f: &(i64, i64), g: &(i64, i64)| (g.0 * f.0, g.0 * f.1 + g.1));
+		q.iter().map(|&(k, l, r, v)| match k {
+		    0 => { seg.add(l..r, (v.signum(), v)); 0 }
+		    1 => { seg.modify(l, (v, 1)); 0 }
+		    _ => seg.sum(l..r).0,
+		}).collect::<Vec<_>>()
+	    }, |(a, q)| {
+		let mut a = a.clone();
+		q.iter().map(|&(k, l, r, v)| match k {
+		    0 => { a[l..r].iter_mut().for_each(|x| *x = v.signum() * *x + v); 0 }
+		    1 => { a[l] = v; 0 }
+		    _ => a[l..r].iter().sum(),
+		}).collect::<Vec<_>>()
+	    });
+	}
+
+	#[test]
+	fn range_map() {
+	    use crate::rangemap::RangeMap;
+	    stress(1000, range_queries, |(a, q)| {
+		let mut map = RangeMap::<usize, i64>::new();
+		for (i, &x) in a.iter().enumerate() { map.overwrite(i, i + 1, x); }
+		q.iter().map(|&(k, l, r, v)| if k == 0 {
+		    map.overwrite(l, r, v);
+		    0
+		} else {
+		    map.fold(l, r, 0, |acc, left, right, value| acc + (right - left) as i64 * value)
+		}).collect::<Vec<_>>()
+	    }, |(a, q)| {
+		let mut a = a.clone();
+		q.iter().map(|&(k, l, r, v)| if k == 0 {
+		    a[l..r].iter_mut().for_each(|x| *x = v);
+		    0
+		} else {
+		    a[l..r].iter().sum()
+		}).collect::<Vec<_>>()
+	    });
+	}
+
+	#[test]
+	fn union_find() {
+	    use crate::union_find::UnionFind;
+	    stress(1000, |rng, size| (size, rng.graph(size, size)), |(n, edges)| {
+		let mut uf = UnionFind::new(*n);
+		edges.iter().map(|&(a, b)| {
+		    uf.link(a, b);
+		    (uf.count_tree(), uf.count_node(a))
+		}).collect::<Vec<_>>()
+	    }, |(n, edges)| {
+		let mut label: Vec<usize> = (0..*n).collect();
+		edges.iter().map(|&(a, b)| {
+		    let (la, lb) = (label[a], label[b]);
+		    label.iter_mut().for_each(|l| if *l == lb { *l = la; });
+		    let mut roots = label.clone();
+		    roots.sort();
+		    roots.dedup();
+		    (roots.len(), label.iter().filter(|&&l| l == label[a]).count())
+		}).collect::<Vec<_>>()
+	    });
+	}
+
+	fn series(rng: &mut XorShift, size: usize, c0: u64) -> Vec<Mint> {
+	    (0..size).map(|i| if i == 0 { Mint::new(c0) } else { Mint::new(rng.next_u64()) }).collect()
+	}
+
+	fn naive_inv(f: &[Mint], k: usize) -> Vec<Mint> {
+	    let mut g = vec![Mint::ZERO; k];
+	    g[0] = f[0].inv();
+	    for n in 1..k {
+		let s = (1..=n.min(f.len() - 1)).fold(Mint::ZERO, |s, i| s + f[i] * g[n - i]);
+		g[n] = -s * g[0];
+	    }
+	    g
+	}
+
+	fn coefficients(p: &Polynomial<Mint>, k: usize) -> Vec<Mint> {
+	    (0..k).map(|i| p.get(i)).collect()
+	}
+
+	#[test]
+	fn polynomial_inv() {
+	    stress(300, |rng, size| {
+		let c0 = 1 + rng.next_u64() % 100;
+		series(rng, size, c0)
+	    }, |f| {
+		coefficients(&Polynomial::from(f.clone()).inv(f.len()), f.len())
+	    }, |f| naive_inv(f, f.len()));
+	}
+
+	#[test]
+	fn polynomial_log_exp() {
+	    // log f = integral(f' / f); (exp f)' = f' exp f
+	    stress(300, |rng, size| series(rng, size, 1), |f| {
+		let p = Polynomial::from(f.clone());
+		let log = p.log(f.len());
+		(coefficients(&log, f.len()), coefficients(&log.exp(f.len()), f.len()))
+	    }, |f| {
+		let k = f.len();
+		let inv = naive_inv(f, k);
+		let mut log = vec![Mint::ZERO; k];
+		for n in 1..k {
+		    let s = (1..=n).fold(Mint::ZERO, |s, i| s + Mint::from(i) * f[i] * inv[n - i]);
+		    log[n] = s / Mint::from(n);
+		}
+		let mut exp = vec![Mint::ZERO; k];
+		exp[0] = Mint::ONE;
+		for n in 1..k {
+		    let s = (1..=n).fold(Mint::ZERO, |s, i| s + Mint::from(i) * log[i] * exp[n - i]);
+		    exp[n] = s / Mint::from(n);
+		}
+		(log, exp)
+	    });
+	}
+    }
+} // mod stress;
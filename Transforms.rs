@@ -0,0 +1,440 @@
+pub mod transforms {
+    // Transforms over arrays indexed by subsets of {0, ..., n - 1}; lengths are 2^n.
+    use crate::matrix::ElemTrait;
+    use std::ops::Div;
+
+    fn check_len(n: usize) {
+	assert!(n.is_power_of_two(), "ERROR: transform length {} is not a power of two", n);
+    }
+
+    // a[s] <- sum_{t subset of s} a[t]
+    pub fn subset_zeta<T: ElemTrait>(a: &mut [T]) {
+	check_len(a.len());
+	let mut w = 1;
+	while w < a.len() {
+	    for s in 0..a.len() {
+		if s & w != 0 { let x = a[s ^ w]; a[s] += x; }
+	    }
+	    w <<= 1;
+	}
+    }
+
+    pub fn subset_mobius<T: ElemTrait>(a: &mut [T]) {
+	check_len(a.len());
+	let mut w = 1;
+	while w < a.len() {
+	    for s in 0..a.len() {
+		if s & w != 0 { let x = a[s ^ w]; a[s] -= x; }
+	    }
+	    w <<= 1;
+	}
+    }
+
+    // a[s] <- sum_{t superset of s} a[t]
+    pub fn superset_zeta<T: ElemTrait>(a: &mut [T]) {
+	check_len(a.len());
+	let mut w = 1;
+	while w < a.len() {
+	    for s in 0..a.len() {
+		if s & w == 0 { let x = a[s | w]; a[s] += x; }
+	    }
+	    w <<= 1;
+	}
+    }
+
+    pub fn superset_mobius<T: ElemTrait>(a: &mut [T]) {
+	check_len(a.len());
+	let mut w = 1;
+	while w < a.len() {
+	    for s in 0..a.len() {
+		if s & w == 0 { let x = a[s | w]; a[s] -= x; }
+	    }
+	    w <<= 1;
+	}
+    }
+
+    // a[s] <- sum_t (-1)^popcount(s & t) a[t]
+    pub fn hadamard<T: ElemTrait>(a: &mut [T]) {
+	check_len(a.len());
+	let mut w = 1;
+	while w < a.len() {
+	    for s in 0..a.len() {
+		if s & w == 0 {
+		    let (x, y) = (a[s], a[s | w]);
+		    a[s] = x + y;
+		    a[s | w] = x - y;
+		}
+	    }
+	    w <<= 1;
+	}
+    }
+
+    fn pointwise<T: ElemTrait>(mut a: Vec<T>, b: &[T]) -> Vec<T> {
+	assert!(a.len() == b.len(), "ERROR: transform lengths differ {} {}", a.len(), b.len());
+	for i in 0..a.len() { a[i] *= b[i]; }
+	a
+    }
+
+    // c[s] = sum_{t | u = s} a[t] b[u]
+    pub fn or_convolution<T: ElemTrait>(a: &[T], b: &[T]) -> Vec<T> {
+	let (mut a, mut b) = (a.to_vec(), b.to_vec());
+	subset_zeta(&mut a);
+	subset_zeta(&mut b);
+	let mut c = pointwise(a, &b);
+	subset_mobius(&mut c);
+	c
+    }
+
+    // c[s] = sum_{t & u = s} a[t] b[u]
+    pub fn and_convolution<T: ElemTrait>(a: &[T], b: &[T]) -> Vec<T> {
+	let (mut a, mut b) = (a.to_vec(), b.to_vec());
+	superset_zeta(&mut a);
+	superset_zeta(&mut b);
+	let mut c = pointwise(a, &b);
+	superset_mobius(&mut c);
+	c
+    }
+
+    // c[s] = sum_{t ^ u = s} a[t] b[u]; divides by the length at the end.
+    pub fn xor_convolution<T: ElemTrait + Div<Output=T> + From<i64>>(a: &[T], b: &[T]) -> Vec<T> {
+	let (mut a, mut b) = (a.to_vec(), b.to_vec());
+	hadamard(&mut a);
+	hadamard(&mut b);
+	let mut c = pointwise(a, &b);
+	hadamard(&mut c);
+	let n = T::from(c.len() as i64);
+	c.iter_mut().for_each(|x| *x = *x / n);
+	c
+    }
+
+    // c[s] = sum_{t subset of s} a[t] b[s \ t], in O(2^n n^2) by ranked zeta transforms.
+    pub fn subset_convolution<T: ElemTrait>(a: &[T], b: &[T]) -> Vec<T> {
+	check_len(a.len());
+	assert!(a.len() == b.len(), "ERROR: transform lengths differ {} {}", a.len(), b.len());
+	let len = a.len();
+	let n = len.trailing_zeros() as usize;
+	let ranked = |a: &[T]| {
+	    let mut r = vec![vec![T::default(); len]; n + 1];
+	    for s in 0..len { r[s.count_ones() as usize][s] = a[s]; }
+	    r.iter_mut().for_each(|r| subset_zeta(r));
+	    r
+	};
+	let (ra, rb) = (ranked(a), ranked(b));
+	let mut c = vec![T::default(); len];
+	let mut rc = vec![T::default(); len];
+	for k in 0..=n {
+	    rc.iter_mut().for_each(|x| *x = T::default());
+	    for i in 0..=k {
+		for s in 0..len { rc[s] += ra[i][s] * rb[k - i][s]; }
+	    }
+	    subset_mobius(&mut rc);
+	    for s in 0..len {
+		if s.count_ones() as usize == k { c[s] = rc[s]; }
+	    }
+	}
+	c
+    }
+
+    // Arrays indexed by 1..=n below; index 0 is ignored.
+
+    fn primes_upto(n: usize) -> Vec<usize> {
+	let mut sieve = vec![true; n + 1];
+	let mut ps = Vec::new();
+	for i in 2..=n {
+	    if sieve[i] {
+		ps.push(i);
+		for j in (i * i..=n).step_by(i) { sieve[j] = false; }
+	    }
+	}
+	ps
+    }
+
+    // a[k] <- sum_{d | k} a[d]
+    pub fn divisor_zeta<T: ElemTrait>(a: &mut [T]) {
+	let n = a.len().saturating_sub(1);
+	for p in primes_upto(n) {
+	    for i in 1..=n / p { let x = a[i]; a[i * p] += x; }
+	}
+    }
+
+    pub fn divisor_mobius<T: ElemTrait>(a: &mut [T]) {
+	let n = a.len().saturating_sub(1);
+	for p in primes_upto(n) {
+	    for i in (1..=n / p).rev() { let x = a[i]; a[i * p] -= x; }
+	}
+    }
+
+    // a[k] <- sum_{k | m} a[m]
+    pub fn multiple_zeta<T: ElemTrait>(a: &mut [T]) {
+	let n = a.len().saturating_sub(1);
+	for p in primes_upto(n) {
+	    for i in (1..=n / p).rev() { let x = a[i * p]; a[i] += x; }
+	}
+    }
+
+    pub fn multiple_mobius<T: ElemTrait>(a: &mut [T]) {
+	let n = a.len().saturating_sub(1);
+	for p in primes_upto(n) {
+	    for i in 1..=n / p { let x = a[i * p]; a[i] -= x; }
+	}
+    }
+
+    // The three convolutions below need a.len() == b.len() and return c of the same length; c[0] is meaningless.
+
+    // c[k] = sum_{gcd(i, j) = k} a[i] b[j]
+    pub fn gcd_convolution<T: ElemTrait>(a: &[T], b: &[T]) -> Vec<T> {
+	let (mut a, mut b) = (a.to_vec(), b.to_vec());
+	multiple_zeta(&mut a);
+	multiple_zeta(&mut b);
+	let mut c = pointwise(a, &b);
+	multiple_mobius(&mut c);
+	c
+    }
+
+    // c[k] = sum_{lcm(i, j) = k} a[i] b[j]
+    pub fn lcm_convolution<T: ElemTrait>(a: &[T], b: &[T]) -> Vec<T> {
+	let (mut a, mut b) = (a.to_vec(), b.to_vec());
+	divisor_zeta(&mut a);
+	divisor_zeta(&mut b);
+	let mut c = pointwise(a, &b);
+	divisor_mobius(&mut c);
+	c
+    }
+
+    // c[k] = sum_{ij = k} a[i] b[j], in O(n log n)
+    pub fn dirichlet_convolution<T: ElemTrait>(a: &[T], b: &[T]) -> Vec<T> {
+	assert!(a.len() == b.len(), "ERROR: transform lengths differ {} {}", a.len(), b.len());
+	let n = a.len();
+	let mut c = vec![T::default(); n];
+	for i in 1..n {
+	    for j in 1..=(n - 1) / i { c[i * j] += a[i] * b[j]; }
+	}
+	c
+    }
+
+    // Du's sieve: F(n) = sum_{k <= n} f(k), given some g with g(1) = 1 and
+    //   sum_g(v) = sum_{k <= v} g(k), sum_fg(v) = sum_{k <= v} (f * g)(k) (Dirichlet product),
+    //   small[v] = F(v) for v < small.len(),
+    // from F(v) = sum_fg(v) - sum_{2 <= d <= v} g(d) F(v / d).
+    // sum_g and sum_fg are called at the values n / x only; small.len() about n^(2/3) is fastest.
+    pub fn du_sieve<T: ElemTrait>(n: u64, small: &[T], sum_g: impl Fn(u64) -> T, sum_fg: impl Fn(u64) -> T) -> T {
+	assert!(!small.is_empty(), "ERROR: du_sieve needs at least small[0]");
+	let limit = small.len() as u64 - 1;
+	if n <= limit { return small[n as usize]; }
+	let k = (n / (limit + 1)) as usize;
+	// big[x] = F(n / x) for x <= k, where n / x > limit
+	let mut big = vec![T::default(); k + 1];
+	for x in (1..=k).rev() {
+	    let v = n / x as u64;
+	    let mut s = sum_fg(v);
+	    let mut d = 2;
+	    while d <= v {
+		let q = v / d;
+		let d2 = v / q;
+		let f = if q <= limit { small[q as usize] } else { big[x * d as usize] };
+		s -= (sum_g(d2) - sum_g(d - 1)) * f;
+		d = d2 + 1;
+	    }
+	    big[x] = s;
+	}
+	big[1]
+    }
+
+    // Prefix sums of totient and of mobius over 0..=n.
+    fn small_totient_mobius<T: ElemTrait + From<i64>>(n: usize) -> (Vec<T>, Vec<T>) {
+	let mut phi: Vec<i64> = (0..=n as i64).collect();
+	let mut mu = vec![1i64; n + 1];
+	for p in primes_upto(n) {
+	    for j in (p..=n).step_by(p) {
+		phi[j] -= phi[j] / p as i64;
+		mu[j] = -mu[j];
+	    }
+	    for j in (p * p..=n).step_by(p * p) { mu[j] = 0; }
+	}
+	let (mut sp, mut sm) = (vec![T::default(); n + 1], vec![T::default(); n + 1]);
+	for i in 1..=n {
+	    sp[i] = sp[i - 1] + T::from(phi[i]);
+	    sm[i] = sm[i - 1] + T::from(mu[i]);
+	}
+	(sp, sm)
+    }
+
+    // A table size for totient_sum and mobius_sum: about n^(2/3), the fastest if it fits in memory.
+    pub fn sieve_limit(n: u64) -> usize {
+	((n as f64).powf(2.0 / 3.0) as usize).max(1)
+    }
+
+    // sum_{k <= n} phi(k), with the sums for k <= limit sieved directly.
+    // T = i64 is exact only for n < 2^32 (n (n + 1) / 2 must fit); use i128 or a ModInt beyond.
+    pub fn totient_sum<T: ElemTrait + From<i64>>(n: u64, limit: usize) -> T {
+	let (small, _) = small_totient_mobius::<T>(limit.min(n as usize));
+	du_sieve(n, &small, |v| T::from(v as i64), |v| {
+	    let (a, b) = if v % 2 == 0 { (v / 2, v + 1) } else { (v, (v + 1) / 2) };
+	    T::from(a as i64) * T::from(b as i64)
+	})
+    }
+
+    // Mertens function sum_{k <= n} mu(k), with the sums for k <= limit sieved directly.
+    pub fn mobius_sum<T: ElemTrait + From<i64>>(n: u64, limit: usize) -> T {
+	let (_, small) = small_totient_mobius::<T>(limit.min(n as usize));
+	du_sieve(n, &small, |v| T::from(v as i64), |_| T::from(1))
+    }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+	use crate::mod_int::ModInt998244353;
+	use crate::stress::XorShift;
+
+	fn sample(n: usize, seed: u64) -> Vec<i64> {
+	    let mut rng = XorShift::new(seed);
+	    (0..n).map(|_| (rng.next_u64() % 21) as i64 - 10).collect()
+	}
+
+	fn naive(a: &[i64], b: &[i64], f: impl Fn(usize, usize) -> Option<usize>) -> Vec<i64> {
+	    let mut c = vec![0; a.len()];
+	    for t in 0..a.len() {
+		for u in 0..b.len() {
+		    if let Some(s) = f(t, u) { c[s] += a[t] * b[u]; }
+		}
+	    }
+	    c
+	}
+
+	#[test]
+	fn zeta_mobius() {
+	    for n in [1, 2, 8, 32] {
+		let a = sample(n, n as u64);
+		let mut z = a.clone();
+		subset_zeta(&mut z);
+		assert_eq!(z, (0..n).map(|s| (0..n).filter(|&t| t & s == t).map(|t| a[t]).sum()).collect::<Vec<i64>>());
+		subset_mobius(&mut z);
+		assert_eq!(z, a);
+		superset_zeta(&mut z);
+		assert_eq!(z, (0..n).map(|s| (0..n).filter(|&t| t & s == s).map(|t| a[t]).sum()).collect::<Vec<i64>>());
+		superset_mobius(&mut z);
+		assert_eq!(z, a);
+	    }
+	}
+
+	#[test]
+	fn convolutions() {
+	    for n in [1, 2, 4, 16, 64] {
+		let (a, b) = (sample(n, 3 + n as u64), sample(n, 5 + n as u64));
+		assert_eq!(or_convolution(&a, &b), naive(&a, &b, |t, u| Some(t | u)));
+		assert_eq!(and_convolution(&a, &b), naive(&a, &b, |t, u| Some(t & u)));
+		assert_eq!(xor_convolution(&a, &b), naive(&a, &b, |t, u| Some(t ^ u)));
+		assert_eq!(subset_convolution(&a, &b), naive(&a, &b, |t, u| if t & u == 0 { Some(t | u) } else { None }));
+
+		type Mint = ModInt998244353;
+		let (ma, mb): (Vec<Mint>, Vec<Mint>) = (a.iter().map(|&x| Mint::from(x)).collect(), b.iter().map(|&x| Mint::from(x)).collect());
+		let c = naive(&a, &b, |t, u| Some(t ^ u));
+		assert_eq!(xor_convolution(&ma, &mb), c.iter().map(|&x| Mint::from(x)).collect::<Vec<_>>());
+	    }
+	}
+
+	#[test]
+	fn divisor_transforms() {
+	    let n = 60;
+	    let (a, b) = (sample(n + 1, 7), sample(n + 1, 9));
+	    let mut z = a.clone();
+	    divisor_zeta(&mut z);
+	    for k in 1..=n { assert_eq!(z[k], (1..=k).filter(|d| k % d == 0).map(|d| a[d]).sum::<i64>()); }
+	    divisor_mobius(&mut z);
+	    assert_eq!(z[1..], a[1..]);
+	    multiple_zeta(&mut z);
+	    for k in 1..=n { assert_eq!(z[k], (k..=n).step_by(k).map(|m| a[m]).sum::<i64>()); }
+	    multiple_mobius(&mut z);
+	    assert_eq!(z[1..], a[1..]);
+
+	    let gcd = |mut x: usize, mut y: usize| { while y != 0 { (x, y) = (y, x % y); } x };
+	    let (mut g, mut l, mut d) = (vec![0; n + 1], vec![0; n + 1], vec![0; n + 1]);
+	    for i in 1..=n {
+		for j in 1..=n {
+		    g[gcd(i, j)] += a[i] * b[j];
+		    if i / gcd(i, j) * j <= n { l[i / gcd(i, j) * j] += a[i] * b[j]; }
+		    if i * j <= n { d[i * j] += a[i] * b[j]; }
+		}
+	    }
+	    assert_eq!(gcd_convolution(&a, &b)[1..], g[1..]);
+	    assert_eq!(lcm_convolution(&a, &b)[1..], l[1..]);
+	    assert_eq!(dirichlet_convolution(&a, &b)[1..], d[1..]);
+	}
+
+	#[test]
+	fn multiplicative_prefix_sums() {
+	    let n = 100000;
+	    let (phi, mu) = small_totient_mobius::<i64>(n);
+	    for k in 1..200 {
+		assert_eq!(phi[k] - phi[k - 1], (1..=k).filter(|&i| (1..=i).all(|d| i % d != 0 || k % d != 0 || d == 1)).count() as i64);
+		let (mut m, mut mu_k) = (k, 1);
+		for p in 2..=k {
+		    if m % p == 0 {
+			m /= p;
+			mu_k = if m % p == 0 { 0 } else { -mu_k };
+		    }
+		}
+		assert_eq!(mu[k] - mu[k - 1], mu_k);
+	    }
+	    for v in [0u64, 1, 2, 10, 99, 1000, 4567, 99999, 100000] {
+		for limit in [1, 50, sieve_limit(v)] {
+		    assert_eq!(totient_sum::<i64>(v, limit), phi[v as usize]);
+		    assert_eq!(mobius_sum::<i64>(v, limit), mu[v as usize]);
+		}
+	    }
+	    let n = 1_000_000_000;
+	    assert_eq!(mobius_sum::<i64>(n, sieve_limit(n)), -222);
+	    type Mint = ModInt998244353;
+	    assert_eq!(totient_sum::<Mint>(100000, 1000), Mint::from(phi[100000]));
+	    assert_eq!(mobius_sum::<Mint>(n, sieve_limit(n)), Mint::from(-222i64));
+
+	    // n (n + 1) / 2 just below 2^63 for i64; the sums past it need i128 or a ModInt
+	    let n = (1 << 32) - 1;
+	    let s = totient_sum::<i128>(n, sieve_limit(n));
+	    assert_eq!(totient_sum::<i64>(n, 1 << 20) as i128, s);
+	    assert_eq!(totient_sum::<Mint>(n, 1 << 20), Mint::from((s % 998244353) as i64));
+	    assert_eq!(totient_sum::<i128>(10_000_000_000, 1 << 20), 30396355092886216366);
+	}
+
+	#[test]
+	fn du_sieve_general() {
+	    // f(k) = k, g = 1, (f * g)(k) = sigma(k)
+	    let sigma_sum = |v: u64| {
+		let (mut s, mut d) = (0i64, 1);
+		while d <= v {
+		    let q = v / d;
+		    let d2 = v / q;
+		    s += (q * ((d + d2) * (d2 - d + 1) / 2)) as i64;
+		    d = d2 + 1;
+		}
+		s
+	    };
+	    let small: Vec<i64> = (0..=1000).map(|k| k * (k + 1) / 2).collect();
+	    for n in [0u64, 7, 1000, 1001, 123456, 1_000_000] {
+		assert_eq!(du_sieve(n, &small, |v| v as i64, sigma_sum), (n * (n + 1) / 2) as i64);
+	    }
+
+	    // f(k) = k mu(k), g(k) = k, f * g = [k = 1]
+	    let n = 100000;
+	    let (_, mu) = small_totient_mobius::<i64>(n);
+	    let mut f = vec![0i64; n + 1];
+	    for k in 1..=n { f[k] = f[k - 1] + k as i64 * (mu[k] - mu[k - 1]); }
+	    for v in [1, 99, 100, 101, 5000, n] {
+		let got = du_sieve(v as u64, &f[..=100], |x| (x * (x + 1) / 2) as i64, |_| 1);
+		assert_eq!(got, f[v]);
+	    }
+	}
+
+	#[test]
+	#[should_panic]
+	fn not_power_of_two() {
+	    subset_zeta(&mut [1i64, 2, 3]);
+	}
+
+	#[test]
+	#[should_panic(expected = "lengths differ")]
+	fn dirichlet_lengths_differ() {
+	    dirichlet_convolution(&[0i64, 1, 2], &[0, 1]);
+	}
+    }
+}
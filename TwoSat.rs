@@ -1,4 +1,4 @@
-mod two_sat {
+pub mod two_sat {
     // graph[i]: i が偶数のとき変数x, i+1がnot x;
     #[derive(Clone, Debug)]
     pub struct TwoSat {
@@ -56,5 +56,35 @@ mod two_sat {
 	    }
 	}
     }
-}
 
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+	use crate::stress::XorShift;
+
+	#[test]
+	fn against_brute_force() {
+	    let n = 4;
+	    let mut rng = XorShift::new(1);
+	    for _ in 0..300 {
+		let mut ts = TwoSat::new();
+		let v = ts.add_nodes(n);
+		let mut clauses = Vec::new();
+		for _ in 0..6 {
+		    let x = rng.next_u64();
+		    let (a, na, b, nb) = (x as usize % n, x >> 8 & 1 == 1, (x >> 16) as usize % n, x >> 24 & 1 == 1);
+		    let (p, q) = (if na { v[a].not() } else { v[a] }, if nb { v[b].not() } else { v[b] });
+		    ts.imply(p, q);
+		    clauses.push((a, na, b, nb));
+		}
+		// p => q
+		let ok = |s: &dyn Fn(usize) -> bool| clauses.iter().all(|&(a, na, b, nb)| s(a) == na || s(b) != nb);
+		let sat = (0..1 << n).any(|m: usize| ok(&|i| m >> i & 1 == 1));
+		match ts.solve() {
+		    Some(t) => assert!(ok(&|i| t.get(v[i]))),
+		    None => assert!(!sat),
+		}
+	    }
+	}
+    }
+}
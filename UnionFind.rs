@@ -1,4 +1,4 @@
-mod union_find {
+pub mod union_find {
     pub struct UnionFind {
         cc: usize,
         a: Vec<isize>,
@@ -45,4 +45,33 @@ mod union_find {
             self.cc
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::stress::XorShift;
+
+        #[test]
+        fn against_labels() {
+            let n = 12;
+            let mut uf = UnionFind::new(n);
+            let mut label: Vec<usize> = (0..n).collect();
+            let mut rng = XorShift::new(1);
+            for _ in 0..40 {
+                let x = rng.next_u64();
+                let (a, b) = (x as usize % n, (x >> 8) as usize % n);
+                uf.link(a, b);
+                let (la, lb) = (label[a], label[b]);
+                label.iter_mut().for_each(|l| if *l == lb { *l = la; });
+                for i in 0..n {
+                    assert_eq!(uf.same(a, i), label[a] == label[i]);
+                    assert_eq!(uf.count_node(i), label.iter().filter(|&&l| l == label[i]).count());
+                }
+                let mut roots = label.clone();
+                roots.sort();
+                roots.dedup();
+                assert_eq!(uf.count_tree(), roots.len());
+            }
+        }
+    }
 } // mod union_find;
@@ -1,12 +1,26 @@
-fn unique<T: Ord + Clone>(v: &mut Vec<T>) {
-    v.sort();
-    let mut len = 0;
-    for i in 0..v.len() {
-	if len == 0 || v[len-1] != v[i] {
-	    let tmp = v[i].clone();
-	    v[len] = tmp;
-	    len += 1;
+pub mod unique {
+    pub fn unique<T: Ord + Clone>(v: &mut Vec<T>) {
+	v.sort();
+	let mut len = 0;
+	for i in 0..v.len() {
+	    if len == 0 || v[len-1] != v[i] {
+		let tmp = v[i].clone();
+		v[len] = tmp;
+		len += 1;
+	    }
+	}
+	while len < v.len() { v.pop(); }
+    }
+
+    #[cfg(test)]
+    mod tests {
+	use super::*;
+
+	#[test]
+	fn sort_dedup() {
+	    let mut v = vec![5, 1, 3, 1, 5, 5, 0];
+	    unique(&mut v);
+	    assert_eq!(v, vec![0, 1, 3, 5]);
 	}
     }
-    while len < v.len() { v.pop(); }
 }
@@ -0,0 +1,49 @@
+// Each snippet file is one self-contained module that is also inlined as is
+// into single-file submissions by Bundler.rs, so the files are included here verbatim
+// and keep their `crate::` paths.
+// The snippets are written for the judges' (older) Rust and in contest style,
+// so the clippy lints below are not followed.
+#![allow(
+    clippy::clone_on_copy,
+    clippy::implicit_saturating_sub,
+    clippy::len_without_is_empty,
+    clippy::manual_div_ceil,
+    clippy::manual_is_multiple_of,
+    clippy::manual_memcpy,
+    clippy::module_inception,
+    clippy::needless_range_loop,
+    clippy::new_without_default,
+    clippy::ptr_arg,
+    clippy::suspicious_op_assign_impl,
+    clippy::while_let_on_iterator,
+)]
+
+// Files defining macros come first: macro_rules! are visible only after their definition.
+include!("Input.rs");
+include!("Output.rs");
+include!("AMinMax.rs");
+include!("BipartiteMatching.rs");
+include!("Bisection.rs");
+include!("BitSet.rs");
+include!("Dijkstra.rs");
+include!("Fenwick.rs");
+include!("Grid.rs");
+include!("Interactive.rs");
+include!("Manacher.rs");
+include!("Matrix.rs");
+include!("Merge.rs");
+include!("Mex.rs");
+include!("ModInt.rs");
+include!("NextPermutation.rs");
+include!("Polynomial.rs");
+include!("PushRelabel.rs");
+include!("RangeMap.rs");
+include!("Rmq.rs");
+include!("Scc.rs");
+include!("SegTree.rs");
+include!("ShortestPath.rs");
+include!("Stress.rs");
+include!("Transforms.rs");
+include!("TwoSat.rs");
+include!("UnionFind.rs");
+include!("Unique.rs");